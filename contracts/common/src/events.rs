@@ -0,0 +1,64 @@
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::governance::GovAction;
+use crate::roles::Role;
+
+// Event topics
+const ROLE_GRANTED: Symbol = symbol_short!("ROLEGRNT");
+const ROLE_REVOKED: Symbol = symbol_short!("ROLERVKD");
+const SIGNERS_CHANGED: Symbol = symbol_short!("SIGNRSCHG");
+const PROPOSAL_CREATED: Symbol = symbol_short!("GOVPROP");
+const PROPOSAL_APPROVED: Symbol = symbol_short!("GOVAPPR");
+const PROPOSAL_EXECUTED: Symbol = symbol_short!("GOVEXEC");
+const UPGRADED: Symbol = symbol_short!("UPGRADED");
+const MIGRATED: Symbol = symbol_short!("MIGRATED");
+
+/// Emit a role granted event
+pub fn emit_role_granted(env: &Env, role: Role, account: &Address, sender: &Address) {
+    env.events()
+        .publish((ROLE_GRANTED, role, account.clone()), sender.clone());
+}
+
+/// Emit a role revoked event
+pub fn emit_role_revoked(env: &Env, role: Role, account: &Address, sender: &Address) {
+    env.events()
+        .publish((ROLE_REVOKED, role, account.clone()), sender.clone());
+}
+
+/// Emit a signer set changed event
+pub fn emit_signers_changed(env: &Env, signers: &Vec<Address>, threshold: u32) {
+    env.events()
+        .publish((SIGNERS_CHANGED,), (signers.clone(), threshold));
+}
+
+/// Emit a proposal created event
+pub fn emit_proposal_created(env: &Env, proposal_id: u64, proposer: &Address, action: &GovAction) {
+    env.events().publish(
+        (PROPOSAL_CREATED, proposal_id),
+        (proposer.clone(), action.clone()),
+    );
+}
+
+/// Emit a proposal approved event with the approvals counted so far
+pub fn emit_proposal_approved(env: &Env, proposal_id: u64, signer: &Address, approvals: u32) {
+    env.events()
+        .publish((PROPOSAL_APPROVED, proposal_id, signer.clone()), approvals);
+}
+
+/// Emit a proposal executed event
+pub fn emit_proposal_executed(env: &Env, proposal_id: u64) {
+    env.events()
+        .publish((PROPOSAL_EXECUTED, proposal_id), env.ledger().timestamp());
+}
+
+/// Emit a contract upgraded event with the previous and new code hashes
+pub fn emit_upgraded(env: &Env, old_wasm_hash: Option<BytesN<32>>, new_wasm_hash: &BytesN<32>) {
+    env.events()
+        .publish((UPGRADED,), (old_wasm_hash, new_wasm_hash.clone()));
+}
+
+/// Emit a storage migrated event
+pub fn emit_migrated(env: &Env, from_version: u32, to_version: u32) {
+    env.events()
+        .publish((MIGRATED,), (from_version, to_version));
+}
@@ -0,0 +1,170 @@
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::events;
+use crate::roles::Role;
+
+// Instance storage keys of the signer set
+pub const SIGNERS_KEY: Symbol = symbol_short!("SIGNERS");
+pub const THRESHOLD_KEY: Symbol = symbol_short!("THRESHOLD");
+pub const PROPOSAL_COUNTER: Symbol = symbol_short!("PROPCNT");
+
+// Persistent entry TTL (in ledgers)
+const DAY_IN_LEDGERS: u32 = 17280;
+const PROPOSAL_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+const PROPOSAL_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+
+// Sensitive action run once enough signers approve it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovAction {
+    SetAdmin(Address),
+    GrantRole(Role, Address),
+    RevokeRole(Role, Address),
+    SetSigners(Vec<Address>, u32), // New signer set and threshold
+    Upgrade(BytesN<32>),           // Hash of the uploaded WASM to install
+}
+
+// Action waiting for signer approvals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub action: GovAction,
+    pub proposer: Address,
+    pub approvals: Vec<Address>, // Signers that approved, including the proposer
+    pub created_at: u64,
+    pub executed: bool,
+}
+
+// Storage key for a proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovKey {
+    Proposal(u64),
+}
+
+/// Get the addresses allowed to propose and approve actions
+pub fn get_signers(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&SIGNERS_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Get the number of approvals an action needs, 0 when no signer set is configured
+pub fn get_threshold(env: &Env) -> u32 {
+    env.storage().instance().get(&THRESHOLD_KEY).unwrap_or(0)
+}
+
+/// Check whether sensitive actions must go through proposals
+pub fn is_enabled(env: &Env) -> bool {
+    get_threshold(env) > 0
+}
+
+/// Check whether an address is in the signer set
+pub fn is_signer(env: &Env, account: &Address) -> bool {
+    get_signers(env).contains(account)
+}
+
+/// Check that a signer set has no duplicates and a threshold between 1 and its size
+pub fn is_valid_signer_set(signers: &Vec<Address>, threshold: u32) -> bool {
+    if threshold == 0 || threshold > signers.len() {
+        return false;
+    }
+
+    for (i, signer) in signers.iter().enumerate() {
+        if signers.first_index_of(&signer) != Some(i as u32) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Replace the signer set and threshold
+/// The caller must have validated the set with `is_valid_signer_set`
+pub fn set_signers(env: &Env, signers: &Vec<Address>, threshold: u32) {
+    env.storage().instance().set(&SIGNERS_KEY, signers);
+    env.storage().instance().set(&THRESHOLD_KEY, &threshold);
+    events::emit_signers_changed(env, signers, threshold);
+}
+
+/// Get a proposal, extending its TTL when found
+pub fn get_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
+    let key = GovKey::Proposal(proposal_id);
+    let proposal: Option<Proposal> = env.storage().persistent().get(&key);
+
+    if proposal.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PROPOSAL_TTL_THRESHOLD, PROPOSAL_TTL_EXTEND_TO);
+    }
+    proposal
+}
+
+/// Store a proposal
+pub fn write_proposal(env: &Env, proposal_id: u64, proposal: &Proposal) {
+    let key = GovKey::Proposal(proposal_id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PROPOSAL_TTL_THRESHOLD, PROPOSAL_TTL_EXTEND_TO);
+}
+
+/// Store a new proposal approved by its proposer and return its ID
+/// The caller must have checked that `proposer` is a signer
+pub fn create_proposal(env: &Env, proposer: &Address, action: &GovAction) -> u64 {
+    let current: u64 = env.storage().instance().get(&PROPOSAL_COUNTER).unwrap_or(0);
+    let proposal_id = current.checked_add(1).expect("Proposal counter overflow");
+    env.storage()
+        .instance()
+        .set(&PROPOSAL_COUNTER, &proposal_id);
+
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    write_proposal(
+        env,
+        proposal_id,
+        &Proposal {
+            action: action.clone(),
+            proposer: proposer.clone(),
+            approvals,
+            created_at: env.ledger().timestamp(),
+            executed: false,
+        },
+    );
+
+    events::emit_proposal_created(env, proposal_id, proposer, action);
+    events::emit_proposal_approved(env, proposal_id, proposer, 1);
+    proposal_id
+}
+
+/// Record a signer's approval of a proposal
+/// The caller must have checked that `signer` is a signer who hasn't approved yet
+pub fn add_approval(env: &Env, proposal_id: u64, proposal: &mut Proposal, signer: &Address) {
+    proposal.approvals.push_back(signer.clone());
+    write_proposal(env, proposal_id, proposal);
+    events::emit_proposal_approved(env, proposal_id, signer, approval_count(env, proposal));
+}
+
+/// Count the approvals of a proposal given by the current signers
+/// Approvals of removed signers no longer count
+pub fn approval_count(env: &Env, proposal: &Proposal) -> u32 {
+    let signers = get_signers(env);
+    proposal
+        .approvals
+        .iter()
+        .filter(|approver| signers.contains(approver))
+        .count() as u32
+}
+
+/// Check whether a proposal has enough approvals to run
+pub fn is_approved(env: &Env, proposal: &Proposal) -> bool {
+    approval_count(env, proposal) >= get_threshold(env)
+}
+
+/// Mark a proposal as executed so it can't run again
+pub fn mark_executed(env: &Env, proposal_id: u64, proposal: &mut Proposal) {
+    proposal.executed = true;
+    write_proposal(env, proposal_id, proposal);
+    events::emit_proposal_executed(env, proposal_id);
+}
@@ -0,0 +1,13 @@
+#![no_std]
+
+// Shared building blocks for the TrustUp contracts
+
+// Module imports
+mod events;
+pub mod governance;
+pub mod roles;
+pub mod upgrade;
+
+// Re-export types for external use
+pub use governance::{GovAction, Proposal};
+pub use roles::{Role, ADMIN_KEY};
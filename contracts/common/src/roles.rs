@@ -0,0 +1,77 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::events;
+
+// Instance storage key of the contract admin, shared by every contract
+pub const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+
+// Persistent entry TTL (in ledgers)
+const DAY_IN_LEDGERS: u32 = 17280;
+const ROLE_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+const ROLE_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+
+// Roles that can be granted to an address
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Admin,       // Every role, plus granting and revoking roles
+    RiskManager, // Tier, limit and fee parameters
+    Pauser,      // Pausing and unpausing entrypoints
+    Collector,   // Marking loans as defaulted
+    Updater,     // Changing reputation scores
+}
+
+// Storage key for a role granted to an address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RoleKey {
+    Role(Role, Address),
+}
+
+/// Read whether a role was granted to or revoked from an address, extending its TTL when found
+/// Returns None when the role was never granted
+pub fn role_entry(env: &Env, role: Role, account: &Address) -> Option<bool> {
+    let key = RoleKey::Role(role, account.clone());
+    let granted: Option<bool> = env.storage().persistent().get(&key);
+
+    if granted.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ROLE_TTL_THRESHOLD, ROLE_TTL_EXTEND_TO);
+    }
+    granted
+}
+
+/// Check whether an address is the contract admin or holds the Admin role
+pub fn is_admin(env: &Env, account: &Address) -> bool {
+    let admin: Option<Address> = env.storage().instance().get(&ADMIN_KEY);
+
+    admin.as_ref() == Some(account) || role_entry(env, Role::Admin, account) == Some(true)
+}
+
+/// Check whether an address holds a role, admins hold every role
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    is_admin(env, account) || role_entry(env, role, account) == Some(true)
+}
+
+/// Grant a role to an address
+/// The caller must have checked that `sender` may manage roles
+pub fn grant_role(env: &Env, sender: &Address, role: Role, account: &Address) {
+    write_role(env, role, account, true);
+    events::emit_role_granted(env, role, account, sender);
+}
+
+/// Revoke a role from an address
+/// Revoked roles keep an explicit entry so an older grant can't resurface
+pub fn revoke_role(env: &Env, sender: &Address, role: Role, account: &Address) {
+    write_role(env, role, account, false);
+    events::emit_role_revoked(env, role, account, sender);
+}
+
+fn write_role(env: &Env, role: Role, account: &Address, granted: bool) {
+    let key = RoleKey::Role(role, account.clone());
+    env.storage().persistent().set(&key, &granted);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ROLE_TTL_THRESHOLD, ROLE_TTL_EXTEND_TO);
+}
@@ -0,0 +1,48 @@
+use soroban_sdk::{symbol_short, BytesN, Env, Symbol};
+
+use crate::events;
+
+// Instance storage keys of the deployed code
+pub const VERSION_KEY: Symbol = symbol_short!("VERSION");
+pub const WASM_HASH_KEY: Symbol = symbol_short!("WASMHASH");
+
+/// Get the storage layout version the contract was last migrated to
+/// Returns 0 for contracts deployed before versions were tracked
+pub fn get_version(env: &Env) -> u32 {
+    env.storage().instance().get(&VERSION_KEY).unwrap_or(0)
+}
+
+/// Record the storage layout version the contract was migrated to
+pub fn set_version(env: &Env, version: u32) {
+    env.storage().instance().set(&VERSION_KEY, &version);
+}
+
+/// Get the hash of the installed code, None for contracts initialized before hashes were tracked
+pub fn get_wasm_hash(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&WASM_HASH_KEY)
+}
+
+/// Record the hash of the code the contract was deployed with
+/// The host can't report a contract's own code hash, so the deployer passes it to `initialize`
+pub fn set_wasm_hash(env: &Env, wasm_hash: &BytesN<32>) {
+    env.storage().instance().set(&WASM_HASH_KEY, wasm_hash);
+}
+
+/// Replace the contract code with an uploaded WASM
+/// The caller must have checked that the upgrade is authorized; the new code runs
+/// from the next invocation, so storage migrations happen in a later `migrate` call
+pub fn upgrade(env: &Env, new_wasm_hash: &BytesN<32>) {
+    let old_wasm_hash = get_wasm_hash(env);
+
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    set_wasm_hash(env, new_wasm_hash);
+
+    events::emit_upgraded(env, old_wasm_hash, new_wasm_hash);
+}
+
+/// Record a finished migration between two storage layout versions
+pub fn finish_migration(env: &Env, from_version: u32, to_version: u32) {
+    set_version(env, to_version);
+    events::emit_migrated(env, from_version, to_version);
+}
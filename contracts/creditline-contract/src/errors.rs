@@ -17,4 +17,36 @@ pub enum CreditLineError {
     Overflow = 10,
     Underflow = 11,
     LoanNotOverdue = 12,
+    CreditLimitExceeded = 13,
+    InvalidTierConfig = 14,
+    InvalidLateFeeConfig = 15,
+    InvalidInstallmentCount = 16,
+    InvalidTenor = 17,
+    DueDateInPast = 18,
+    DueDatesNotIncreasing = 19,
+    ScheduleAmountMismatch = 20,
+    InvalidScheduleLimits = 21,
+    TooManyActiveLoans = 22,
+    ExposureLimitExceeded = 23,
+    InvalidExposureLimits = 24,
+    InvalidRebate = 25,
+    NotMerchant = 26,
+    NoRestructureProposal = 27,
+    ScheduleBelowBalance = 28,
+    ContractPaused = 29,
+    NoPendingAdmin = 30,
+    MissingRole = 31,
+    ChangeNotFound = 32,
+    TimelockNotExpired = 33,
+    InvalidTimelockDelay = 34,
+    NotSigner = 35,
+    ProposalNotFound = 36,
+    ProposalExecuted = 37,
+    AlreadyApproved = 38,
+    InvalidSignerSet = 39,
+    GovernanceRequired = 40,
+    AlreadyMigrated = 41,
+    LoanRestructured = 42,
+    RestructureChanged = 43,
+    LoansOutstanding = 44,
 }
@@ -1,10 +1,24 @@
 use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
 
-use crate::types::RepaymentInstallment;
+use crate::types::{LoanPayoff, LoanRefund, ParamChange, RepaymentInstallment};
 
 // Event topics
 const LOAN_CREATED: Symbol = symbol_short!("LOANCRTD");
 const LOAN_DEFAULTED: Symbol = symbol_short!("LOANDFLT");
+const LOAN_REPAID: Symbol = symbol_short!("REPAYMNT");
+const LOAN_PAID: Symbol = symbol_short!("LOANPAID");
+const LATE_FEE: Symbol = symbol_short!("LATEFEE");
+const LOAN_PAID_OFF: Symbol = symbol_short!("LOANPOFF");
+const LOAN_REFUNDED: Symbol = symbol_short!("LOANRFND");
+const LOAN_RESTRUCTURED: Symbol = symbol_short!("LOANRSTR");
+const PAUSE_CHANGED: Symbol = symbol_short!("PAUSECHG");
+const ADMIN_PROPOSED: Symbol = symbol_short!("ADMINPROP");
+const ADMIN_CHANGED: Symbol = symbol_short!("ADMINCHGD");
+const ADMIN_TRANSFER_CANCELLED: Symbol = symbol_short!("ADMINCNCL");
+const CHANGE_QUEUED: Symbol = symbol_short!("CHGQUEUED");
+const CHANGE_EXECUTED: Symbol = symbol_short!("CHGEXECD");
+const CHANGE_CANCELLED: Symbol = symbol_short!("CHGCNCLD");
+const REPUTATION_FAILED: Symbol = symbol_short!("REPFAILED");
 
 /// Emit a loan created event
 pub fn emit_loan_created(
@@ -45,3 +59,167 @@ pub fn emit_loan_defaulted(
         ),
     );
 }
+
+/// Emit a repayment event with the amount applied and the balance still owed
+pub fn emit_loan_repaid(
+    env: &Env,
+    borrower: &Address,
+    loan_id: u64,
+    amount: i128,
+    remaining_balance: i128,
+) {
+    env.events().publish(
+        (LOAN_REPAID, borrower, loan_id),
+        (amount, remaining_balance, env.ledger().timestamp()),
+    );
+}
+
+/// Emit a loan paid event once the full balance has been repaid
+pub fn emit_loan_paid(env: &Env, borrower: &Address, loan_id: u64, total_amount: i128) {
+    env.events().publish(
+        (LOAN_PAID, borrower, loan_id),
+        (total_amount, env.ledger().timestamp()),
+    );
+}
+
+/// Emit a late fee event for an installment past its grace period
+pub fn emit_late_fee(env: &Env, borrower: &Address, loan_id: u64, due_date: u64, fee: i128) {
+    env.events().publish(
+        (LATE_FEE, borrower, loan_id),
+        (due_date, fee, env.ledger().timestamp()),
+    );
+}
+
+/// Emit a loan paid off event when a borrower settles a loan ahead of schedule
+pub fn emit_loan_paid_off(
+    env: &Env,
+    borrower: &Address,
+    loan_id: u64,
+    payoff: &LoanPayoff,
+    rebate: i128,
+) {
+    env.events().publish(
+        (LOAN_PAID_OFF, borrower, loan_id),
+        (
+            payoff.principal,
+            payoff.interest,
+            payoff.fees,
+            rebate,
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit a loan refunded event when a merchant returns funds for a purchase
+pub fn emit_loan_refunded(
+    env: &Env,
+    merchant: &Address,
+    loan_id: u64,
+    refund: &LoanRefund,
+    remaining_balance: i128,
+) {
+    env.events().publish(
+        (LOAN_REFUNDED, merchant, loan_id),
+        (
+            refund.to_pool,
+            refund.to_borrower,
+            refund.guarantee_returned,
+            remaining_balance,
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit a loan restructured event when an approved schedule replaces the current one
+pub fn emit_loan_restructured(
+    env: &Env,
+    borrower: &Address,
+    loan_id: u64,
+    old_balance: i128,
+    new_balance: i128,
+    installments: u32,
+) {
+    env.events().publish(
+        (LOAN_RESTRUCTURED, borrower, loan_id),
+        (
+            old_balance,
+            new_balance,
+            installments,
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit a pause changed event with the previous and new pause flags
+pub fn emit_pause_changed(env: &Env, admin: &Address, old_flags: u32, new_flags: u32) {
+    env.events().publish(
+        (PAUSE_CHANGED, admin),
+        (old_flags, new_flags, env.ledger().timestamp()),
+    );
+}
+
+/// Emit an admin proposed event when a transfer to a new admin is started
+pub fn emit_admin_proposed(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_PROPOSED, admin),
+        (pending_admin.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit an admin changed event when the proposed admin accepts
+pub fn emit_admin_changed(env: &Env, old_admin: &Address, new_admin: &Address) {
+    env.events().publish(
+        (ADMIN_CHANGED,),
+        (
+            old_admin.clone(),
+            new_admin.clone(),
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit an admin transfer cancelled event
+pub fn emit_admin_transfer_cancelled(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_TRANSFER_CANCELLED, admin),
+        (pending_admin.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit a parameter change queued event
+pub fn emit_change_queued(
+    env: &Env,
+    caller: &Address,
+    change_id: u64,
+    change: &ParamChange,
+    eta: u64,
+) {
+    env.events().publish(
+        (CHANGE_QUEUED, change_id),
+        (caller.clone(), change.clone(), eta),
+    );
+}
+
+/// Emit a parameter change executed event
+pub fn emit_change_executed(env: &Env, caller: &Address, change_id: u64, change: &ParamChange) {
+    env.events().publish(
+        (CHANGE_EXECUTED, change_id),
+        (caller.clone(), change.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit a parameter change cancelled event
+pub fn emit_change_cancelled(env: &Env, caller: &Address, change_id: u64) {
+    env.events().publish(
+        (CHANGE_CANCELLED, change_id),
+        (caller.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit a reputation hook failed event when a score update was skipped
+pub fn emit_reputation_failed(env: &Env, borrower: &Address, hook: Symbol) {
+    env.events().publish(
+        (REPUTATION_FAILED, borrower),
+        (hook, env.ledger().timestamp()),
+    );
+}
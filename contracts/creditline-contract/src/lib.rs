@@ -1,6 +1,8 @@
 #![no_std]
+use common::{governance, roles, upgrade};
+use interfaces::{LiquidityPoolClient, MerchantRegistryClient, ReputationClient};
 use soroban_sdk::{
-    contract, contractimpl, panic_with_error, symbol_short, Address, Env, IntoVal, Symbol, Vec,
+    contract, contractimpl, panic_with_error, symbol_short, token, Address, BytesN, Env, Vec,
 };
 
 // Module imports
@@ -11,65 +13,1469 @@ mod storage;
 mod types;
 
 // Re-export types for external use
+pub use common::{GovAction, Proposal, Role};
 pub use errors::CreditLineError;
-pub use types::{Loan, LoanStatus, RepaymentInstallment};
+pub use types::{
+    BorrowerExposure, CreditTier, ExposureLimits, LateFeeConfig, Loan, LoanPayoff, LoanRefund,
+    LoanStatus, ParamChange, QueuedChange, RepaymentInstallment, ReputationHook,
+    RestructureProposal, ScheduleLimits, ScheduleRevision, TierConfig, PAUSE_ALL, PAUSE_COLLECTION,
+    PAUSE_ORIGINATION, PAUSE_REFUND, PAUSE_REPAYMENT, PAUSE_RESTRUCTURE,
+};
+
+/// CreditLine contract structure
+#[contract]
+pub struct CreditLineContract;
+
+/// Contract implementation
+#[contractimpl]
+impl CreditLineContract {
+    /// Get the storage version the contract was last migrated to
+    /// Set to this code's version at initialize and bumped by `migrate` after each upgrade
+    pub fn get_version(env: Env) -> u32 {
+        upgrade::get_version(&env)
+    }
+
+    /// Initialize the contract with admin, external contract addresses and the deployed code hash
+    /// Can only be called once (when admin is not set)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        reputation_contract: Address,
+        merchant_registry: Address,
+        liquidity_pool: Address,
+        wasm_hash: BytesN<32>,
+    ) {
+        // Check if already initialized
+        let admin_opt: Option<Address> = env.storage().instance().get(&storage::ADMIN_KEY);
+        if admin_opt.is_some() {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+
+        storage::set_admin(&env, &admin);
+        storage::set_reputation_contract(&env, &reputation_contract);
+        storage::set_merchant_registry(&env, &merchant_registry);
+        storage::set_liquidity_pool(&env, &liquidity_pool);
+        upgrade::set_version(&env, types::CONTRACT_VERSION);
+        upgrade::set_wasm_hash(&env, &wasm_hash);
+    }
+
+    /// Install new contract code from an uploaded WASM hash (admin only)
+    /// Once a signer set is configured, use an `Upgrade` proposal instead.
+    /// The new code runs from the next call, which should be `migrate`
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+        access::require_no_governance(&env);
+
+        upgrade::upgrade(&env, &new_wasm_hash);
+    }
+
+    /// Bring storage from the stored version up to this code's version
+    /// Anyone can call this after an upgrade, each version bump migrates once.
+    /// Returns the new storage version
+    pub fn migrate(env: Env) -> Result<u32, CreditLineError> {
+        let from_version = upgrade::get_version(&env);
+        if from_version >= types::CONTRACT_VERSION {
+            return Err(CreditLineError::AlreadyMigrated);
+        }
+
+        // Layout changes are applied here, one step per version. Deployments from
+        // before versions were tracked (version 0) share the version 1 layout, their
+        // legacy loan map is moved in batches with `migrate_loans`
+
+        upgrade::finish_migration(&env, from_version, types::CONTRACT_VERSION);
+        Ok(types::CONTRACT_VERSION)
+    }
+
+    /// Get the hash of the installed code, recorded at initialize and on every upgrade
+    pub fn get_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        upgrade::get_wasm_hash(&env)
+    }
+
+    /// Create a new loan repaid in equal installments every `period_secs`
+    /// Validates all requirements and creates an active loan
+    pub fn create_loan(
+        env: Env,
+        user: Address,
+        merchant: Address,
+        total_amount: i128,
+        guarantee_amount: i128,
+        installments: u32,
+        period_secs: u64,
+    ) -> u64 {
+        user.require_auth();
+        access::require_not_paused(&env, types::PAUSE_ORIGINATION);
+
+        let principal = Self::validate_guarantee(&env, total_amount, guarantee_amount);
+        let principal_schedule =
+            Self::equal_principal_schedule(&env, principal, installments, period_secs);
+
+        let tier = Self::validate_loan(&env, &user, &merchant, principal, &principal_schedule);
+
+        let (schedule, interest_amount) =
+            Self::price_schedule(&env, tier.apr_bps, &principal_schedule);
+        let schedule = Self::equalize_installments(&env, &schedule, interest_amount);
+
+        Self::open_loan(
+            &env,
+            &user,
+            &merchant,
+            total_amount,
+            guarantee_amount,
+            principal,
+            &tier,
+            schedule,
+            interest_amount,
+        )
+    }
+
+    /// Create a new loan with a custom repayment schedule
+    /// Validates all requirements and creates an active loan
+    /// Installment amounts are the principal due on each date and must add up to the
+    /// financed amount, the contract adds the interest of the borrower's tier on top
+    pub fn create_loan_with_schedule(
+        env: Env,
+        user: Address,
+        merchant: Address,
+        total_amount: i128,
+        guarantee_amount: i128,
+        repayment_schedule: Vec<RepaymentInstallment>,
+    ) -> u64 {
+        user.require_auth();
+        access::require_not_paused(&env, types::PAUSE_ORIGINATION);
+
+        let principal = Self::validate_guarantee(&env, total_amount, guarantee_amount);
+
+        let tier = Self::validate_loan(&env, &user, &merchant, principal, &repayment_schedule);
+
+        let (schedule, interest_amount) =
+            Self::price_schedule(&env, tier.apr_bps, &repayment_schedule);
+
+        Self::open_loan(
+            &env,
+            &user,
+            &merchant,
+            total_amount,
+            guarantee_amount,
+            principal,
+            &tier,
+            schedule,
+            interest_amount,
+        )
+    }
+
+    /// Quote the equal-installment schedule `create_loan` would generate for a user now
+    /// Amounts include the interest of the user's tier
+    pub fn quote_schedule(
+        env: Env,
+        user: Address,
+        total_amount: i128,
+        guarantee_amount: i128,
+        installments: u32,
+        period_secs: u64,
+    ) -> Vec<RepaymentInstallment> {
+        let principal = Self::validate_guarantee(&env, total_amount, guarantee_amount);
+        let principal_schedule =
+            Self::equal_principal_schedule(&env, principal, installments, period_secs);
+        Self::validate_schedule(&env, principal, &principal_schedule);
+
+        let tier = Self::validate_reputation(&env, &user, principal);
+
+        let (schedule, interest_amount) =
+            Self::price_schedule(&env, tier.apr_bps, &principal_schedule);
+        Self::equalize_installments(&env, &schedule, interest_amount)
+    }
+
+    /// Get a loan by ID
+    pub fn get_loan(env: Env, loan_id: u64) -> Loan {
+        storage::read_loan(&env, loan_id)
+            .unwrap_or_else(|| panic_with_error!(&env, CreditLineError::LoanNotFound))
+    }
+
+    /// Get a page of a borrower's loans, oldest first
+    /// At most MAX_PAGE_SIZE loans are returned per call
+    pub fn get_loans_by_borrower(env: Env, user: Address, start: u32, limit: u32) -> Vec<Loan> {
+        Self::loan_page(&env, &types::LoanIndex::Borrower(user), start, limit)
+    }
+
+    /// Get a page of a merchant's loans, oldest first
+    /// At most MAX_PAGE_SIZE loans are returned per call
+    pub fn get_loans_by_merchant(env: Env, merchant: Address, start: u32, limit: u32) -> Vec<Loan> {
+        Self::loan_page(&env, &types::LoanIndex::Merchant(merchant), start, limit)
+    }
+
+    /// Get the number of loans a borrower currently has open
+    pub fn get_active_loan_count(env: Env, user: Address) -> u32 {
+        storage::get_exposure(&env, &user).active_loans
+    }
+
+    /// Get a borrower's open loans and unpaid principal
+    pub fn get_borrower_exposure(env: Env, user: Address) -> BorrowerExposure {
+        storage::get_exposure(&env, &user)
+    }
+
+    /// Get the exposure limits that apply to a tier, or the default limits when `tier` is None
+    pub fn get_exposure_limits(env: Env, tier: Option<CreditTier>) -> ExposureLimits {
+        match tier {
+            Some(tier) => Self::exposure_limits_for(&env, tier),
+            None => storage::get_exposure_limits(&env),
+        }
+    }
+
+    /// Get the principal, interest and fees needed to settle an active loan now
+    /// Interest accrued so far is owed in full, interest not accrued yet is reduced
+    /// by the configured payoff rebate
+    pub fn get_loan_payoff(env: Env, loan_id: u64) -> Result<LoanPayoff, CreditLineError> {
+        let loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
+
+        Self::payoff_quote(&env, &loan).map(|(payoff, _)| payoff)
+    }
+
+    /// Settle an active loan in full ahead of schedule
+    /// The borrower pays the payoff amount to the liquidity pool and the guarantee is
+    /// released. The early payment reputation bonus is only earned once the loan is at
+    /// least EARLY_PAYMENT_WINDOW old, so borrowing and settling at once cannot farm score
+    pub fn payoff_loan(
+        env: Env,
+        borrower: Address,
+        loan_id: u64,
+    ) -> Result<LoanPayoff, CreditLineError> {
+        borrower.require_auth();
+        access::require_not_paused(&env, types::PAUSE_REPAYMENT);
+
+        let mut loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.borrower != borrower {
+            return Err(CreditLineError::NotBorrower);
+        }
+
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
+
+        let (payoff, rebate) = Self::payoff_quote(&env, &loan)?;
+
+        // Pay the pool, fees are paid to liquidity providers as yield
+        let token_client = token::Client::new(&env, &Self::token_address(&env));
+        let pool = Self::liquidity_pool(&env);
+        token_client.transfer(&borrower, &pool.address, &payoff.total);
+
+        let yield_amount = payoff
+            .interest
+            .checked_add(payoff.fees)
+            .ok_or(CreditLineError::Overflow)?;
+        pool.receive_repayment(
+            &env.current_contract_address(),
+            &payoff.principal,
+            &yield_amount,
+        );
+
+        // Close out every installment
+        for (index, mut installment) in loan.repayment_schedule.iter().enumerate() {
+            installment.paid_amount = installment.amount;
+            loan.repayment_schedule.set(index as u32, installment);
+        }
+
+        loan.principal_paid = loan.principal;
+        loan.interest_paid = loan
+            .interest_paid
+            .checked_add(payoff.interest)
+            .ok_or(CreditLineError::Overflow)?;
+        loan.accrued_interest = Self::accrued_interest(&loan, env.ledger().timestamp())?;
+        loan.fees = 0;
+        loan.remaining_balance = 0;
+        loan.status = LoanStatus::Paid;
+
+        Self::reduce_exposure(&env, &borrower, payoff.principal, true);
+
+        // Release the escrowed guarantee back to the borrower
+        token_client.transfer(
+            &env.current_contract_address(),
+            &borrower,
+            &loan.guarantee_amount,
+        );
+
+        storage::write_loan(&env, &loan);
+
+        events::emit_loan_paid_off(&env, &borrower, loan_id, &payoff, rebate);
+
+        let loan_age = env.ledger().timestamp().saturating_sub(loan.created_at);
+        if loan_age >= types::EARLY_PAYMENT_WINDOW {
+            Self::update_reputation(&env, &borrower, ReputationHook::Early);
+        }
+
+        Ok(payoff)
+    }
+
+    /// Refund part or all of a purchase financed by an active loan
+    /// `amount` is the share of the financed principal the merchant returns. It is split
+    /// between the pool, for principal still owed, and the borrower, for principal they
+    /// already repaid, and the same share of the guarantee and of the unpaid interest is
+    /// released. Interest already paid stays with the pool and reputation is unchanged.
+    /// Restructured loans cannot be refunded, their schedule no longer shows what was repaid
+    pub fn refund_loan(
+        env: Env,
+        merchant: Address,
+        loan_id: u64,
+        amount: i128,
+    ) -> Result<LoanRefund, CreditLineError> {
+        merchant.require_auth();
+        access::require_not_paused(&env, types::PAUSE_REFUND);
+
+        let mut loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.merchant != merchant {
+            return Err(CreditLineError::NotMerchant);
+        }
+
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
+
+        if loan.restructured_at.is_some() {
+            return Err(CreditLineError::LoanRestructured);
+        }
+
+        if amount <= 0 || amount > loan.principal {
+            return Err(CreditLineError::InvalidAmount);
+        }
+
+        // Shrink every installment by the refunded share, cumulative rounding keeps the
+        // principal cut equal to `amount`
+        let base = loan.principal;
+        let mut principal_seen: i128 = 0;
+        let mut principal_cut: i128 = 0;
+        let mut interest_seen: i128 = 0;
+        let mut interest_cut: i128 = 0;
+        let mut to_borrower: i128 = 0;
+        let mut to_pool: i128 = 0;
+        let mut interest_waived: i128 = 0;
+
+        for (index, mut installment) in loan.repayment_schedule.iter().enumerate() {
+            let interest_paid = installment.paid_amount.min(installment.interest);
+            let principal_paid = installment
+                .paid_amount
+                .checked_sub(interest_paid)
+                .ok_or(CreditLineError::Underflow)?;
+
+            principal_seen = principal_seen
+                .checked_add(principal_paid)
+                .ok_or(CreditLineError::Overflow)?;
+            let refunded = Self::share_of(principal_seen, amount, base)?
+                .checked_sub(principal_cut)
+                .ok_or(CreditLineError::Underflow)?;
+            principal_cut = principal_cut
+                .checked_add(refunded)
+                .ok_or(CreditLineError::Overflow)?;
+
+            principal_seen = installment
+                .principal
+                .checked_sub(principal_paid)
+                .and_then(|v| principal_seen.checked_add(v))
+                .ok_or(CreditLineError::Overflow)?;
+            let retired = Self::share_of(principal_seen, amount, base)?
+                .checked_sub(principal_cut)
+                .ok_or(CreditLineError::Underflow)?;
+            principal_cut = principal_cut
+                .checked_add(retired)
+                .ok_or(CreditLineError::Overflow)?;
+
+            interest_seen = installment
+                .interest
+                .checked_sub(interest_paid)
+                .and_then(|v| interest_seen.checked_add(v))
+                .ok_or(CreditLineError::Overflow)?;
+            let waived = Self::share_of(interest_seen, amount, base)?
+                .checked_sub(interest_cut)
+                .ok_or(CreditLineError::Underflow)?;
+            interest_cut = interest_cut
+                .checked_add(waived)
+                .ok_or(CreditLineError::Overflow)?;
+
+            installment.principal = refunded
+                .checked_add(retired)
+                .and_then(|cut| installment.principal.checked_sub(cut))
+                .ok_or(CreditLineError::Underflow)?;
+            installment.interest = installment
+                .interest
+                .checked_sub(waived)
+                .ok_or(CreditLineError::Underflow)?;
+            installment.amount = installment
+                .principal
+                .checked_add(installment.interest)
+                .ok_or(CreditLineError::Overflow)?;
+            installment.paid_amount = installment
+                .paid_amount
+                .checked_sub(refunded)
+                .ok_or(CreditLineError::Underflow)?;
+            loan.repayment_schedule.set(index as u32, installment);
+
+            to_borrower = to_borrower
+                .checked_add(refunded)
+                .ok_or(CreditLineError::Overflow)?;
+            to_pool = to_pool
+                .checked_add(retired)
+                .ok_or(CreditLineError::Overflow)?;
+            interest_waived = interest_waived
+                .checked_add(waived)
+                .ok_or(CreditLineError::Overflow)?;
+        }
+
+        let refund = LoanRefund {
+            to_pool,
+            to_borrower,
+            guarantee_returned: Self::share_of(loan.guarantee_amount, amount, base)?,
+            interest_waived,
+        };
+
+        // The merchant returns the outstanding share to the pool and the repaid share
+        // straight to the borrower
+        let token_client = token::Client::new(&env, &Self::token_address(&env));
+        let pool = Self::liquidity_pool(&env);
+        if refund.to_pool > 0 {
+            token_client.transfer(&merchant, &pool.address, &refund.to_pool);
+            pool.receive_repayment(&env.current_contract_address(), &refund.to_pool, &0);
+        }
+        if refund.to_borrower > 0 {
+            token_client.transfer(&merchant, &loan.borrower, &refund.to_borrower);
+        }
+
+        loan.principal = loan
+            .principal
+            .checked_sub(amount)
+            .ok_or(CreditLineError::Underflow)?;
+        loan.principal_paid = loan
+            .principal_paid
+            .checked_sub(refund.to_borrower)
+            .ok_or(CreditLineError::Underflow)?;
+        loan.interest_amount = loan
+            .interest_amount
+            .checked_sub(refund.interest_waived)
+            .ok_or(CreditLineError::Underflow)?;
+        loan.capitalized_interest = loan.capitalized_interest.min(loan.interest_amount);
+        loan.guarantee_amount = loan
+            .guarantee_amount
+            .checked_sub(refund.guarantee_returned)
+            .ok_or(CreditLineError::Underflow)?;
+        loan.total_amount = amount
+            .checked_add(refund.guarantee_returned)
+            .and_then(|returned| loan.total_amount.checked_sub(returned))
+            .ok_or(CreditLineError::Underflow)?;
+
+        let closed = loan.principal == 0;
+        if closed {
+            // Nothing is left to finance, outstanding late fees go with the purchase
+            loan.fees = 0;
+            loan.status = if refund.to_borrower > 0 {
+                LoanStatus::Refunded
+            } else {
+                LoanStatus::Cancelled
+            };
+        }
+
+        let unpaid_interest = loan
+            .interest_amount
+            .checked_sub(loan.interest_paid)
+            .ok_or(CreditLineError::Underflow)?;
+        loan.remaining_balance = Self::outstanding_principal(&loan)
+            .checked_add(unpaid_interest)
+            .and_then(|v| v.checked_add(loan.fees))
+            .ok_or(CreditLineError::Overflow)?;
+        loan.accrued_interest = Self::accrued_interest(&loan, env.ledger().timestamp())?;
+
+        // A partial refund can leave nothing owed on what the borrower kept
+        let settled = !closed && loan.remaining_balance == 0;
+        if settled {
+            loan.status = LoanStatus::Paid;
+        }
+
+        Self::reduce_exposure(&env, &loan.borrower, refund.to_pool, closed || settled);
+
+        let guarantee_released = if settled {
+            refund
+                .guarantee_returned
+                .checked_add(loan.guarantee_amount)
+                .ok_or(CreditLineError::Overflow)?
+        } else {
+            refund.guarantee_returned
+        };
+        if guarantee_released > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &loan.borrower,
+                &guarantee_released,
+            );
+        }
+
+        storage::write_loan(&env, &loan);
+
+        events::emit_loan_refunded(&env, &merchant, loan_id, &refund, loan.remaining_balance);
+
+        if settled {
+            events::emit_loan_paid(&env, &loan.borrower, loan_id, loan.total_amount);
+        }
+
+        Ok(refund)
+    }
+
+    /// Propose a new repayment schedule for an active loan
+    /// The borrower or a risk manager may propose, and the proposal counts as their approval.
+    /// Installment amounts are the full amounts due and must add up to at least the
+    /// balance owed on the current schedule, any excess is charged as extra interest.
+    /// Returns the proposal ID the other party approves
+    pub fn propose_restructure(
+        env: Env,
+        proposer: Address,
+        loan_id: u64,
+        new_schedule: Vec<RepaymentInstallment>,
+    ) -> Result<u64, CreditLineError> {
+        proposer.require_auth();
+        access::require_not_paused(&env, types::PAUSE_RESTRUCTURE);
+
+        let loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
+
+        let is_borrower = proposer == loan.borrower;
+        if !is_borrower {
+            access::require_role(&env, Role::RiskManager, &proposer);
+        }
+
+        let total = Self::validate_installments(&env, &new_schedule);
+        if total < Self::scheduled_balance(&loan)? {
+            return Err(CreditLineError::ScheduleBelowBalance);
+        }
+
+        // A new proposal replaces any earlier one and its approvals
+        let proposal_id = storage::increment_restructure_counter(&env);
+        storage::set_restructure(
+            &env,
+            loan_id,
+            &RestructureProposal {
+                proposal_id,
+                schedule: new_schedule,
+                proposed_at: env.ledger().timestamp(),
+                borrower_approved: is_borrower,
+                admin_approved: !is_borrower,
+            },
+        );
+        Ok(proposal_id)
+    }
+
+    /// Approve the pending restructure of a loan as its borrower or as a risk manager
+    /// Once both have approved, the new schedule replaces the current one, the old
+    /// schedule is kept in the loan's history and the borrower takes the restructure
+    /// reputation penalty. `proposal_id` must name the proposal that was reviewed, a
+    /// replaced proposal fails with `RestructureChanged`. Returns true when the restructure was applied
+    pub fn approve_restructure(
+        env: Env,
+        approver: Address,
+        loan_id: u64,
+        proposal_id: u64,
+    ) -> Result<bool, CreditLineError> {
+        approver.require_auth();
+        access::require_not_paused(&env, types::PAUSE_RESTRUCTURE);
+
+        let mut proposal = storage::get_restructure(&env, loan_id)
+            .ok_or(CreditLineError::NoRestructureProposal)?;
+        if proposal.proposal_id != proposal_id {
+            return Err(CreditLineError::RestructureChanged);
+        }
+        let mut loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
+
+        if approver == loan.borrower {
+            proposal.borrower_approved = true;
+        } else {
+            access::require_role(&env, Role::RiskManager, &approver);
+            proposal.admin_approved = true;
+        }
+
+        if !(proposal.borrower_approved && proposal.admin_approved) {
+            storage::set_restructure(&env, loan_id, &proposal);
+            return Ok(false);
+        }
+
+        // Repayments since the proposal may have changed the balance, check again
+        let total = Self::validate_installments(&env, &proposal.schedule);
+        let balance = Self::scheduled_balance(&loan)?;
+        if total < balance {
+            return Err(CreditLineError::ScheduleBelowBalance);
+        }
+
+        let now = env.ledger().timestamp();
+        let principal = Self::outstanding_principal(&loan);
+        let interest = total - principal;
+
+        // Split every installment into principal and interest in proportion to its amount
+        let mut schedule = Vec::new(&env);
+        let mut seen: i128 = 0;
+        let mut allocated: i128 = 0;
+        for installment in proposal.schedule.iter() {
+            seen += installment.amount;
+            let installment_interest = Self::share_of(seen, interest, total)? - allocated;
+            allocated += installment_interest;
+
+            schedule.push_back(RepaymentInstallment {
+                due_date: installment.due_date,
+                amount: installment.amount,
+                principal: installment.amount - installment_interest,
+                interest: installment_interest,
+                paid_amount: 0,
+                late: false,
+                missed: false,
+            });
+        }
+
+        // Interest accrued so far stays owed in full, the rest accrues over the new term
+        let accrued = Self::accrued_interest(&loan, now)?
+            .max(loan.interest_paid)
+            .min(loan.interest_amount);
+
+        storage::push_schedule_history(
+            &env,
+            loan_id,
+            &ScheduleRevision {
+                replaced_at: now,
+                schedule: loan.repayment_schedule.clone(),
+            },
+        );
+
+        let old_balance = loan.remaining_balance;
+        loan.repayment_schedule = schedule;
+        loan.interest_amount = loan
+            .interest_paid
+            .checked_add(interest)
+            .ok_or(CreditLineError::Overflow)?;
+        loan.remaining_balance = total
+            .checked_add(loan.fees)
+            .ok_or(CreditLineError::Overflow)?;
+        loan.restructured_at = Some(now);
+        loan.capitalized_interest = accrued;
+        loan.accrued_interest = Self::accrued_interest(&loan, now)?;
+
+        storage::write_loan(&env, &loan);
+        storage::remove_restructure(&env, loan_id);
+
+        events::emit_loan_restructured(
+            &env,
+            &loan.borrower,
+            loan_id,
+            old_balance,
+            loan.remaining_balance,
+            loan.repayment_schedule.len(),
+        );
+
+        Self::update_reputation(&env, &loan.borrower, ReputationHook::Restructure);
+
+        Ok(true)
+    }
+
+    /// Withdraw the pending restructure of a loan (borrower or risk manager)
+    pub fn cancel_restructure(
+        env: Env,
+        caller: Address,
+        loan_id: u64,
+    ) -> Result<(), CreditLineError> {
+        caller.require_auth();
+
+        let loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+        if caller != loan.borrower {
+            access::require_role(&env, Role::RiskManager, &caller);
+        }
+
+        if storage::get_restructure(&env, loan_id).is_none() {
+            return Err(CreditLineError::NoRestructureProposal);
+        }
+
+        storage::remove_restructure(&env, loan_id);
+        Ok(())
+    }
+
+    /// Get the pending restructure proposal for a loan, if any
+    pub fn get_restructure_proposal(env: Env, loan_id: u64) -> Option<RestructureProposal> {
+        storage::get_restructure(&env, loan_id)
+    }
+
+    /// Get the schedules a loan had before its restructures, oldest first
+    pub fn get_schedule_history(env: Env, loan_id: u64) -> Vec<ScheduleRevision> {
+        storage::get_schedule_history(&env, loan_id)
+    }
+
+    /// Get the share of unaccrued interest waived on early payoff, in basis points
+    pub fn get_payoff_rebate(env: Env) -> u32 {
+        storage::get_payoff_rebate(&env)
+    }
+
+    /// Pause every pausable entrypoint (pauser only)
+    pub fn pause(env: Env, pauser: Address) {
+        Self::set_paused(env, pauser, types::PAUSE_ALL);
+    }
+
+    /// Resume every paused entrypoint (pauser only)
+    pub fn unpause(env: Env, pauser: Address) {
+        Self::set_paused(env, pauser, 0);
+    }
+
+    /// Pause only the entrypoint groups in `flags` and resume the rest (pauser only)
+    /// e.g. `PAUSE_ORIGINATION` stops new loans while repayments keep working,
+    /// bits outside `PAUSE_ALL` are ignored
+    pub fn set_paused(env: Env, pauser: Address, flags: u32) {
+        pauser.require_auth();
+        access::require_role(&env, Role::Pauser, &pauser);
+
+        let flags = flags & types::PAUSE_ALL;
+        let old_flags = storage::get_paused(&env);
+        storage::set_paused(&env, flags);
+        events::emit_pause_changed(&env, &pauser, old_flags, flags);
+    }
+
+    /// Get the pause flags currently set, 0 when nothing is paused
+    pub fn get_paused(env: Env) -> u32 {
+        storage::get_paused(&env)
+    }
+
+    /// Start transferring the admin role to a new address (admin only)
+    /// The transfer only takes effect once the new address calls `accept_admin`.
+    /// Once a signer set is configured, use a `SetAdmin` proposal instead
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        access::require_no_governance(&env);
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_pending_admin(&env, &new_admin);
+        events::emit_admin_proposed(&env, &admin, &new_admin);
+    }
+
+    /// Complete a pending admin transfer, authorized by the proposed address
+    /// Transfers started before a signer set was configured can no longer complete
+    pub fn accept_admin(env: Env) -> Result<(), CreditLineError> {
+        access::require_no_governance(&env);
+        let new_admin = storage::get_pending_admin(&env).ok_or(CreditLineError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        let old_admin = storage::get_admin(&env);
+        storage::set_admin(&env, &new_admin);
+        storage::remove_pending_admin(&env);
+        events::emit_admin_changed(&env, &old_admin, &new_admin);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), CreditLineError> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let pending_admin =
+            storage::get_pending_admin(&env).ok_or(CreditLineError::NoPendingAdmin)?;
+        storage::remove_pending_admin(&env);
+        events::emit_admin_transfer_cancelled(&env, &admin, &pending_admin);
+        Ok(())
+    }
+
+    /// Grant a role to an address (admin only)
+    /// Once a signer set is configured, use a `GrantRole` proposal instead
+    pub fn grant_role(env: Env, admin: Address, role: Role, account: Address) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+        access::require_no_governance(&env);
+
+        roles::grant_role(&env, &admin, role, &account);
+    }
+
+    /// Revoke a role from an address (admin only)
+    /// Once a signer set is configured, use a `RevokeRole` proposal instead
+    pub fn revoke_role(env: Env, admin: Address, role: Role, account: Address) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+        access::require_no_governance(&env);
+
+        roles::revoke_role(&env, &admin, role, &account);
+    }
+
+    /// Check if an address holds a role, the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        roles::has_role(&env, role, &account)
+    }
+
+    /// Configure the first signer set for governance proposals (admin only)
+    /// From then on admin changes and role grants need `threshold` signer approvals,
+    /// and the signer set itself can only change through a `SetSigners` proposal
+    pub fn set_signers(
+        env: Env,
+        admin: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), CreditLineError> {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        if governance::is_enabled(&env) {
+            return Err(CreditLineError::GovernanceRequired);
+        }
+        if !governance::is_valid_signer_set(&signers, threshold) {
+            return Err(CreditLineError::InvalidSignerSet);
+        }
+
+        governance::set_signers(&env, &signers, threshold);
+        Ok(())
+    }
+
+    /// Propose a governance action (signers only), counting as the proposer's approval
+    /// Returns the proposal ID, the action runs right away when one approval is enough
+    pub fn propose_action(
+        env: Env,
+        signer: Address,
+        action: GovAction,
+    ) -> Result<u64, CreditLineError> {
+        signer.require_auth();
+        if !governance::is_signer(&env, &signer) {
+            return Err(CreditLineError::NotSigner);
+        }
+        Self::validate_action(&action)?;
+
+        let proposal_id = governance::create_proposal(&env, &signer, &action);
+        let mut proposal =
+            governance::get_proposal(&env, proposal_id).ok_or(CreditLineError::ProposalNotFound)?;
+        Self::execute_if_approved(&env, proposal_id, &mut proposal)?;
+        Ok(proposal_id)
+    }
+
+    /// Approve a governance proposal (signers only)
+    /// The action runs once approvals from the current signers reach the threshold.
+    /// Returns true when the action was executed
+    pub fn approve(env: Env, signer: Address, proposal_id: u64) -> Result<bool, CreditLineError> {
+        signer.require_auth();
+        if !governance::is_signer(&env, &signer) {
+            return Err(CreditLineError::NotSigner);
+        }
+
+        let mut proposal =
+            governance::get_proposal(&env, proposal_id).ok_or(CreditLineError::ProposalNotFound)?;
+        if proposal.executed {
+            return Err(CreditLineError::ProposalExecuted);
+        }
+        if proposal.approvals.contains(&signer) {
+            return Err(CreditLineError::AlreadyApproved);
+        }
+
+        governance::add_approval(&env, proposal_id, &mut proposal, &signer);
+        Self::execute_if_approved(&env, proposal_id, &mut proposal)
+    }
+
+    /// Get a governance proposal and its approvals
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        governance::get_proposal(&env, proposal_id)
+    }
+
+    /// Get the signers allowed to propose and approve governance actions
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        governance::get_signers(&env)
+    }
+
+    /// Get the approvals a governance action needs, 0 when no signer set is configured
+    pub fn get_threshold(env: Env) -> u32 {
+        governance::get_threshold(&env)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        storage::get_admin(&env)
+    }
+
+    /// Get the address proposed as the next admin, if a transfer is pending
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Queue a change to an external contract address or a risk parameter
+    /// Address changes require the admin, risk parameters a risk manager. The change is
+    /// validated now and can be executed once the timelock delay has passed.
+    /// Returns the id of the queued change
+    pub fn queue_change(
+        env: Env,
+        caller: Address,
+        change: ParamChange,
+    ) -> Result<u64, CreditLineError> {
+        caller.require_auth();
+        Self::require_change_role(&env, &caller, &change);
+        Self::validate_change(&env, &change)?;
+
+        let queued_at = env.ledger().timestamp();
+        let eta = queued_at
+            .checked_add(storage::get_timelock_delay(&env))
+            .ok_or(CreditLineError::Overflow)?;
+
+        let change_id = storage::increment_change_counter(&env);
+        storage::set_queued_change(
+            &env,
+            change_id,
+            &QueuedChange {
+                change: change.clone(),
+                queued_at,
+                eta,
+            },
+        );
+
+        events::emit_change_queued(&env, &caller, change_id, &change, eta);
+        Ok(change_id)
+    }
+
+    /// Apply a queued change once its timelock delay has passed
+    /// Requires the same role that was needed to queue it
+    pub fn execute_change(
+        env: Env,
+        caller: Address,
+        change_id: u64,
+    ) -> Result<(), CreditLineError> {
+        caller.require_auth();
+
+        let queued =
+            storage::get_queued_change(&env, change_id).ok_or(CreditLineError::ChangeNotFound)?;
+        Self::require_change_role(&env, &caller, &queued.change);
+
+        if env.ledger().timestamp() < queued.eta {
+            return Err(CreditLineError::TimelockNotExpired);
+        }
+
+        // Parameters may depend on state that changed while queued, check again
+        Self::validate_change(&env, &queued.change)?;
+        Self::apply_change(&env, &queued.change);
+        storage::remove_queued_change(&env, change_id);
+
+        events::emit_change_executed(&env, &caller, change_id, &queued.change);
+        Ok(())
+    }
+
+    /// Drop a queued change before it is executed
+    /// Requires the same role that was needed to queue it
+    pub fn cancel_change(env: Env, caller: Address, change_id: u64) -> Result<(), CreditLineError> {
+        caller.require_auth();
+
+        let queued =
+            storage::get_queued_change(&env, change_id).ok_or(CreditLineError::ChangeNotFound)?;
+        Self::require_change_role(&env, &caller, &queued.change);
+
+        storage::remove_queued_change(&env, change_id);
+        events::emit_change_cancelled(&env, &caller, change_id);
+        Ok(())
+    }
+
+    /// Get a queued change, if it has not been executed or cancelled
+    pub fn get_queued_change(env: Env, change_id: u64) -> Option<QueuedChange> {
+        storage::get_queued_change(&env, change_id)
+    }
+
+    /// Get the delay in seconds between queueing and executing a change
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        storage::get_timelock_delay(&env)
+    }
+
+    /// Get the stablecoin token address
+    pub fn get_token(env: Env) -> Address {
+        Self::token_address(&env)
+    }
+
+    /// Get the credit tier table, ordered from the highest to the lowest score
+    pub fn get_credit_tiers(env: Env) -> Vec<TierConfig> {
+        storage::get_credit_tiers(&env)
+    }
+
+    /// Repay part or all of an active loan
+    /// Funds are transferred from the borrower to the liquidity pool and applied
+    /// to the outstanding installments in due-date order
+    pub fn repay_loan(
+        env: Env,
+        borrower: Address,
+        loan_id: u64,
+        amount: i128,
+    ) -> Result<LoanStatus, CreditLineError> {
+        borrower.require_auth();
+        access::require_not_paused(&env, types::PAUSE_REPAYMENT);
+
+        // 1. Validation: Loan must exist and belong to the caller
+        let mut loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.borrower != borrower {
+            return Err(CreditLineError::NotBorrower);
+        }
+
+        // 2. Validation: Loan must be Active
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
 
-/// CreditLine contract structure
-#[contract]
-pub struct CreditLineContract;
+        // 3. Validation: Amount must be positive and not exceed what is owed
+        if amount <= 0 || amount > loan.remaining_balance {
+            return Err(CreditLineError::InvalidAmount);
+        }
 
-/// Contract implementation
-#[contractimpl]
-impl CreditLineContract {
-    /// Get the version of this contract
-    pub fn get_version() -> Symbol {
-        symbol_short!("v1_0_0")
+        // 4. Transfer repayment to Liquidity Pool
+        let token_client = token::Client::new(&env, &Self::token_address(&env));
+        let lp_address =
+            storage::get_liquidity_pool(&env).ok_or(CreditLineError::InsufficientLiquidity)?;
+        let pool = LiquidityPoolClient::new(&env, &lp_address);
+
+        token_client.transfer(&borrower, &pool.address, &amount);
+
+        // 5. Settle outstanding fees, then apply the rest to installments
+        let fees_repaid = loan.fees.min(amount);
+        loan.fees -= fees_repaid;
+
+        let principal_before = loan.principal_paid;
+        let interest_before = loan.interest_paid;
+        let completed_due_dates =
+            Self::apply_to_installments(&env, &mut loan, amount - fees_repaid)?;
+
+        loan.remaining_balance = loan
+            .remaining_balance
+            .checked_sub(amount)
+            .ok_or(CreditLineError::Underflow)?;
+        loan.accrued_interest = Self::accrued_interest(&loan, env.ledger().timestamp())?;
+
+        let principal_repaid = loan
+            .principal_paid
+            .checked_sub(principal_before)
+            .ok_or(CreditLineError::Underflow)?;
+        // Late fees are paid to liquidity providers as yield
+        let interest_repaid = loan
+            .interest_paid
+            .checked_sub(interest_before)
+            .and_then(|v| v.checked_add(fees_repaid))
+            .ok_or(CreditLineError::Underflow)?;
+
+        pool.receive_repayment(
+            &env.current_contract_address(),
+            &principal_repaid,
+            &interest_repaid,
+        );
+
+        Self::reduce_exposure(
+            &env,
+            &borrower,
+            principal_repaid,
+            loan.remaining_balance == 0,
+        );
+
+        if loan.remaining_balance == 0 {
+            loan.status = LoanStatus::Paid;
+
+            // Release the escrowed guarantee back to the borrower
+            token_client.transfer(
+                &env.current_contract_address(),
+                &borrower,
+                &loan.guarantee_amount,
+            );
+        }
+
+        storage::write_loan(&env, &loan);
+
+        // 6. Emit Events
+        events::emit_loan_repaid(&env, &borrower, loan_id, amount, loan.remaining_balance);
+
+        if loan.status == LoanStatus::Paid {
+            events::emit_loan_paid(&env, &borrower, loan_id, loan.total_amount);
+        }
+
+        // 7. Reward repayment behavior for every installment this payment completed
+        let now = env.ledger().timestamp();
+        for due_date in completed_due_dates.iter() {
+            if now.saturating_add(types::EARLY_PAYMENT_WINDOW) <= due_date {
+                Self::update_reputation(&env, &borrower, ReputationHook::Early);
+            } else if now <= due_date {
+                Self::update_reputation(&env, &borrower, ReputationHook::OnTime);
+            }
+        }
+
+        if loan.status == LoanStatus::Paid {
+            Self::update_reputation(&env, &borrower, ReputationHook::Completion);
+        }
+
+        Ok(loan.status)
     }
 
-    /// Initialize the contract with admin and external contract addresses
-    /// Can only be called once (when admin is not set)
-    pub fn initialize(
-        env: Env,
-        admin: Address,
-        reputation_contract: Address,
-        merchant_registry: Address,
-        liquidity_pool: Address,
-    ) {
-        // Check if already initialized
-        let admin_opt: Option<Address> = env.storage().instance().get(&storage::ADMIN_KEY);
-        if admin_opt.is_some() {
-            panic!("Already initialized");
+    /// Assess late fees on every installment past its grace period
+    /// Anyone may call this; each installment is charged at most once and the borrower
+    /// is penalized once when it turns late and once more when it is missed
+    /// Returns the total fees added to the loan balance by this call
+    pub fn assess_late_fees(env: Env, loan_id: u64) -> Result<i128, CreditLineError> {
+        access::require_not_paused(&env, types::PAUSE_COLLECTION);
+        let mut loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
+
+        if loan.status != LoanStatus::Active {
+            return Err(CreditLineError::LoanNotActive);
+        }
+
+        let config = storage::get_late_fee_config(&env);
+        let now = env.ledger().timestamp();
+        let mut assessed: i128 = 0;
+
+        for (index, mut installment) in loan.repayment_schedule.iter().enumerate() {
+            let unpaid = installment
+                .amount
+                .checked_sub(installment.paid_amount)
+                .ok_or(CreditLineError::Underflow)?;
+            let overdue_for = now.saturating_sub(installment.due_date);
+
+            if unpaid == 0 || overdue_for <= config.grace_period {
+                continue;
+            }
+
+            if !installment.late {
+                let fee = unpaid
+                    .checked_mul(config.percent_bps as i128)
+                    .map(|v| v / types::BPS_DENOMINATOR)
+                    .and_then(|v| v.checked_add(config.flat_fee))
+                    .ok_or(CreditLineError::Overflow)?;
+
+                installment.late = true;
+                assessed = assessed.checked_add(fee).ok_or(CreditLineError::Overflow)?;
+
+                events::emit_late_fee(&env, &loan.borrower, loan_id, installment.due_date, fee);
+
+                // A missed installment only takes the larger missed penalty below
+                if overdue_for <= types::MISSED_PAYMENT_PERIOD {
+                    Self::update_reputation(&env, &loan.borrower, ReputationHook::Late);
+                }
+            }
+
+            if !installment.missed && overdue_for > types::MISSED_PAYMENT_PERIOD {
+                installment.missed = true;
+                Self::update_reputation(&env, &loan.borrower, ReputationHook::Missed);
+            }
+
+            loan.repayment_schedule.set(index as u32, installment);
         }
 
+        loan.fees = loan
+            .fees
+            .checked_add(assessed)
+            .ok_or(CreditLineError::Overflow)?;
+        loan.remaining_balance = loan
+            .remaining_balance
+            .checked_add(assessed)
+            .ok_or(CreditLineError::Overflow)?;
+        loan.accrued_interest = Self::accrued_interest(&loan, now)?;
+
+        storage::write_loan(&env, &loan);
+
+        Ok(assessed)
+    }
+
+    /// Get the grace period and late fee charged per installment
+    pub fn get_late_fee_config(env: Env) -> LateFeeConfig {
+        storage::get_late_fee_config(&env)
+    }
+
+    /// Get the installment count and tenor bounds for new loans
+    pub fn get_schedule_limits(env: Env) -> ScheduleLimits {
+        storage::get_schedule_limits(&env)
+    }
+
+    /// Move up to `limit` loans from the legacy instance map to per-loan storage (admin only)
+    /// Each loan is converted to the current layout, added to the borrower and merchant
+    /// indexes and, while active, counted in the borrower's exposure and the active loans.
+    /// Loans already written under their own key are newer and are kept as they are
+    /// Returns the number of loans left to migrate
+    pub fn migrate_loans(env: Env, admin: Address, limit: u32) -> u32 {
         admin.require_auth();
+        access::require_admin(&env, &admin);
 
-        storage::set_admin(&env, &admin);
-        storage::set_reputation_contract(&env, &reputation_contract);
-        storage::set_merchant_registry(&env, &merchant_registry);
-        storage::set_liquidity_pool(&env, &liquidity_pool);
+        let Some(mut loans) = storage::read_legacy_loans(&env) else {
+            return 0;
+        };
+
+        for loan_id in loans.keys().iter().take(limit as usize) {
+            if let Some(legacy) = loans.get(loan_id) {
+                if !storage::has_loan_entry(&env, loan_id) {
+                    let loan = legacy.into_loan(&env);
+                    storage::write_loan(&env, &loan);
+                    storage::index_loan(&env, &loan);
+
+                    if loan.status == LoanStatus::Active {
+                        let mut exposure = storage::get_exposure(&env, &loan.borrower);
+                        exposure.active_loans += 1;
+                        exposure.outstanding_principal += loan.principal - loan.principal_paid;
+                        storage::set_exposure(&env, &loan.borrower, &exposure);
+                        storage::set_active_loan_count(
+                            &env,
+                            storage::get_active_loan_count(&env) + 1,
+                        );
+                    }
+                }
+            }
+            loans.remove(loan_id);
+        }
+
+        storage::write_legacy_loans(&env, &loans);
+        loans.len()
     }
 
-    /// Create a new loan
-    /// Validates all requirements and creates an active loan
-    pub fn create_loan(
-        env: Env,
-        user: Address,
-        merchant: Address,
+    /// Get the configured stablecoin token address
+    fn token_address(env: &Env) -> Address {
+        storage::get_token(env).unwrap_or_else(|| panic!("Token not configured"))
+    }
+
+    /// Get a client for the configured Reputation contract, None while it is not set
+    /// Score hooks need this contract to hold the `Updater` role in the Reputation contract
+    fn reputation(env: &Env) -> Option<ReputationClient<'_>> {
+        storage::get_reputation_contract(env)
+            .map(|reputation_contract| ReputationClient::new(env, &reputation_contract))
+    }
+
+    /// Run a score hook on the Reputation contract without letting it block the loan flow
+    /// A failed hook, e.g. while Reputation is paused, is skipped and reported with a REPFAILED event
+    fn update_reputation(env: &Env, borrower: &Address, hook: ReputationHook) {
+        let Some(reputation) = Self::reputation(env) else {
+            return;
+        };
+        let updater = env.current_contract_address();
+        let (name, updated) = match hook {
+            ReputationHook::OnTime => (
+                symbol_short!("on_time"),
+                reputation.try_reward_on_time(&updater, borrower).is_ok(),
+            ),
+            ReputationHook::Early => (
+                symbol_short!("early"),
+                reputation.try_reward_early(&updater, borrower).is_ok(),
+            ),
+            ReputationHook::Completion => (
+                symbol_short!("complete"),
+                reputation.try_reward_completion(&updater, borrower).is_ok(),
+            ),
+            ReputationHook::Late => (
+                symbol_short!("late"),
+                reputation.try_penalize_late(&updater, borrower).is_ok(),
+            ),
+            ReputationHook::Missed => (
+                symbol_short!("missed"),
+                reputation.try_penalize_missed(&updater, borrower).is_ok(),
+            ),
+            ReputationHook::Restructure => (
+                symbol_short!("restruct"),
+                reputation
+                    .try_penalize_restructure(&updater, borrower)
+                    .is_ok(),
+            ),
+            ReputationHook::Slash => (
+                symbol_short!("slash"),
+                reputation.try_slash(&updater, borrower).is_ok(),
+            ),
+        };
+        if !updated {
+            events::emit_reputation_failed(env, borrower, name);
+        }
+    }
+
+    /// Apply a repayment to the unpaid installments, earliest due date first
+    /// Within an installment interest is paid before principal
+    /// Returns the due dates of the installments this repayment settled in full
+    fn apply_to_installments(
+        env: &Env,
+        loan: &mut Loan,
+        amount: i128,
+    ) -> Result<Vec<u64>, CreditLineError> {
+        let mut completed = Vec::new(env);
+        let mut left = amount;
+
+        while left > 0 {
+            // Find the unpaid installment with the earliest due date
+            let mut next: Option<(u32, RepaymentInstallment)> = None;
+            for (index, installment) in loan.repayment_schedule.iter().enumerate() {
+                if installment.paid_amount >= installment.amount {
+                    continue;
+                }
+                let is_earlier = match &next {
+                    Some((_, current)) => installment.due_date < current.due_date,
+                    None => true,
+                };
+                if is_earlier {
+                    next = Some((index as u32, installment));
+                }
+            }
+
+            let Some((index, mut installment)) = next else {
+                // Every installment is settled, any surplus only reduces the balance
+                break;
+            };
+
+            let outstanding = installment
+                .amount
+                .checked_sub(installment.paid_amount)
+                .ok_or(CreditLineError::Underflow)?;
+            let applied = outstanding.min(left);
+
+            let interest_before = installment.paid_amount.min(installment.interest);
+            installment.paid_amount = installment
+                .paid_amount
+                .checked_add(applied)
+                .ok_or(CreditLineError::Overflow)?;
+            left -= applied;
+
+            let interest_applied =
+                installment.paid_amount.min(installment.interest) - interest_before;
+            loan.interest_paid = loan
+                .interest_paid
+                .checked_add(interest_applied)
+                .ok_or(CreditLineError::Overflow)?;
+            loan.principal_paid = loan
+                .principal_paid
+                .checked_add(applied - interest_applied)
+                .ok_or(CreditLineError::Overflow)?;
+
+            if installment.paid_amount == installment.amount {
+                completed.push_back(installment.due_date);
+            }
+
+            loan.repayment_schedule.set(index, installment);
+        }
+
+        Ok(completed)
+    }
+
+    /// Validate guarantee amount is at least 20% of total amount and leaves something to finance
+    /// Returns the principal financed by the pool
+    fn validate_guarantee(env: &Env, total_amount: i128, guarantee_amount: i128) -> i128 {
+        if total_amount <= 0 || guarantee_amount <= 0 || guarantee_amount >= total_amount {
+            panic_with_error!(env, CreditLineError::InvalidAmount);
+        }
+
+        // Calculate minimum guarantee (20% of total)
+        let min_guarantee = total_amount
+            .checked_mul(types::MIN_GUARANTEE_PERCENT)
+            .and_then(|v| v.checked_div(100))
+            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+
+        if guarantee_amount < min_guarantee {
+            panic_with_error!(env, CreditLineError::InsufficientGuarantee);
+        }
+
+        total_amount
+            .checked_sub(guarantee_amount)
+            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Underflow))
+    }
+
+    /// Read the loans for a slice of an index
+    fn loan_page(env: &Env, index: &types::LoanIndex, start: u32, limit: u32) -> Vec<Loan> {
+        let end = start
+            .saturating_add(limit.min(types::MAX_PAGE_SIZE))
+            .min(storage::get_loan_index_len(env, index));
+
+        let mut loans = Vec::new(env);
+        for slot in start..end {
+            if let Some(loan) = storage::read_indexed_loan(env, index, slot)
+                .and_then(|id| storage::read_loan(env, id))
+            {
+                loans.push_back(loan);
+            }
+        }
+        loans
+    }
+
+    /// Release repaid or written-off principal from a borrower's exposure
+    fn reduce_exposure(env: &Env, borrower: &Address, principal: i128, closed: bool) {
+        let mut exposure = storage::get_exposure(env, borrower);
+        exposure.outstanding_principal = (exposure.outstanding_principal - principal).max(0);
+        if closed {
+            exposure.active_loans = exposure.active_loans.saturating_sub(1);
+            storage::set_active_loan_count(
+                env,
+                storage::get_active_loan_count(env).saturating_sub(1),
+            );
+        }
+        storage::set_exposure(env, borrower, &exposure);
+    }
+
+    /// Exposure limits for a tier, falling back to the default limits
+    fn exposure_limits_for(env: &Env, tier: CreditTier) -> ExposureLimits {
+        storage::get_tier_exposure_limits(env)
+            .get(tier)
+            .unwrap_or_else(|| storage::get_exposure_limits(env))
+    }
+
+    /// Validate a new loan keeps the borrower within the limits of their tier
+    fn validate_exposure(env: &Env, user: &Address, tier: CreditTier, principal: i128) {
+        let limits = Self::exposure_limits_for(env, tier);
+        let exposure = storage::get_exposure(env, user);
+
+        if exposure.active_loans >= limits.max_active_loans {
+            panic_with_error!(env, CreditLineError::TooManyActiveLoans);
+        }
+
+        let outstanding = exposure
+            .outstanding_principal
+            .checked_add(principal)
+            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+        if outstanding > limits.max_outstanding {
+            panic_with_error!(env, CreditLineError::ExposureLimitExceeded);
+        }
+    }
+
+    /// Run the create_loan checks that follow `validate_guarantee` on the financed principal
+    /// Returns the tier the loan is priced at
+    fn validate_loan(
+        env: &Env,
+        user: &Address,
+        merchant: &Address,
+        principal: i128,
+        schedule: &Vec<RepaymentInstallment>,
+    ) -> TierConfig {
+        Self::validate_schedule(env, principal, schedule);
+
+        Self::validate_merchant(env, merchant);
+
+        let tier = Self::validate_reputation(env, user, principal);
+
+        Self::validate_exposure(env, user, tier.tier, principal);
+
+        Self::validate_liquidity(env, principal);
+
+        tier
+    }
+
+    /// Escrow the guarantee, fund the merchant from the pool and record the loan
+    #[allow(clippy::too_many_arguments)]
+    fn open_loan(
+        env: &Env,
+        user: &Address,
+        merchant: &Address,
         total_amount: i128,
         guarantee_amount: i128,
-        repayment_schedule: Vec<RepaymentInstallment>,
+        principal: i128,
+        tier: &TierConfig,
+        schedule: Vec<RepaymentInstallment>,
+        interest_amount: i128,
     ) -> u64 {
-        user.require_auth();
-
-        Self::validate_guarantee(&env, total_amount, guarantee_amount);
-
-        Self::validate_merchant(&env, &merchant);
+        // Hold the guarantee in escrow until the loan is settled
+        let token_address = Self::token_address(env);
+        token::Client::new(env, &token_address).transfer(
+            user,
+            &env.current_contract_address(),
+            &guarantee_amount,
+        );
 
-        Self::validate_reputation(&env, &user);
+        // Pool pays the financed portion to the merchant's payout address
+        Self::liquidity_pool(env).fund_loan(
+            &env.current_contract_address(),
+            &Self::merchant_payout_address(env, merchant),
+            &principal,
+        );
 
-        Self::validate_liquidity(&env, total_amount, guarantee_amount);
+        let loan_id = storage::increment_loan_counter(env);
 
-        let loan_id = storage::increment_loan_counter(&env);
+        let remaining_balance = principal
+            .checked_add(interest_amount)
+            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
 
         // Create loan record
         let loan = Loan {
@@ -78,148 +1484,550 @@ impl CreditLineContract {
             merchant: merchant.clone(),
             total_amount,
             guarantee_amount,
-            remaining_balance: total_amount,
-            repayment_schedule: repayment_schedule.clone(),
+            remaining_balance,
+            repayment_schedule: schedule.clone(),
             status: LoanStatus::Active,
             created_at: env.ledger().timestamp(),
+            tier: tier.tier,
+            apr_bps: tier.apr_bps,
+            principal,
+            interest_amount,
+            accrued_interest: 0,
+            principal_paid: 0,
+            interest_paid: 0,
+            fees: 0,
+            restructured_at: None,
+            capitalized_interest: 0,
         };
 
-        storage::write_loan(&env, &loan);
+        storage::write_loan(env, &loan);
+        storage::index_loan(env, &loan);
+        let mut exposure = storage::get_exposure(env, user);
+        exposure.active_loans += 1;
+        exposure.outstanding_principal += principal;
+        storage::set_exposure(env, user, &exposure);
+        storage::set_active_loan_count(env, storage::get_active_loan_count(env) + 1);
 
         events::emit_loan_created(
-            &env,
-            &user,
-            &merchant,
+            env,
+            user,
+            merchant,
             loan_id,
             total_amount,
             guarantee_amount,
-            &repayment_schedule,
+            &schedule,
         );
 
         loan_id
     }
 
-    /// Get a loan by ID
-    pub fn get_loan(env: Env, loan_id: u64) -> Loan {
-        storage::read_loan(&env, loan_id)
-            .unwrap_or_else(|| panic_with_error!(&env, CreditLineError::LoanNotFound))
+    /// Split the principal into equal portions due every `period_secs` from now
+    /// Any rounding remainder is added to the last installment. The count is checked
+    /// against the schedule limits first so an oversized request fails before the loop
+    fn equal_principal_schedule(
+        env: &Env,
+        principal: i128,
+        installments: u32,
+        period_secs: u64,
+    ) -> Vec<RepaymentInstallment> {
+        let limits = storage::get_schedule_limits(env);
+        if installments < limits.min_installments || installments > limits.max_installments {
+            panic_with_error!(env, CreditLineError::InvalidInstallmentCount);
+        }
+
+        let mut schedule = Vec::new(env);
+
+        let now = env.ledger().timestamp();
+        let portion = principal / installments as i128;
+        let remainder = principal - portion * installments as i128;
+
+        for i in 1..=installments {
+            let due_date = (i as u64)
+                .checked_mul(period_secs)
+                .and_then(|v| v.checked_add(now))
+                .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+            let amount = if i == installments {
+                portion + remainder
+            } else {
+                portion
+            };
+
+            schedule.push_back(RepaymentInstallment {
+                due_date,
+                amount,
+                principal: amount,
+                interest: 0,
+                paid_amount: 0,
+                late: false,
+                missed: false,
+            });
+        }
+
+        schedule
     }
 
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let old_admin = storage::get_admin(&env);
-        old_admin.require_auth();
-        access::require_admin(&env, &old_admin);
+    /// Add simple interest on each principal portion from now until its due date
+    /// Returns the priced schedule and its total interest
+    /// Repayment progress is tracked by the contract, never taken from the caller
+    fn price_schedule(
+        env: &Env,
+        apr_bps: u32,
+        principal_schedule: &Vec<RepaymentInstallment>,
+    ) -> (Vec<RepaymentInstallment>, i128) {
+        let now = env.ledger().timestamp();
+        let mut schedule = Vec::new(env);
+        let mut interest_amount: i128 = 0;
 
-        storage::set_admin(&env, &new_admin);
+        for installment in principal_schedule.iter() {
+            let interest = Self::interest_for(
+                installment.amount,
+                apr_bps,
+                installment.due_date.saturating_sub(now),
+            )
+            .unwrap_or_else(|e| panic_with_error!(env, e));
+
+            let amount = installment
+                .amount
+                .checked_add(interest)
+                .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+            interest_amount = interest_amount
+                .checked_add(interest)
+                .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+
+            schedule.push_back(RepaymentInstallment {
+                due_date: installment.due_date,
+                amount,
+                principal: installment.amount,
+                interest,
+                paid_amount: 0,
+                late: false,
+                missed: false,
+            });
+        }
+
+        (schedule, interest_amount)
     }
 
-    pub fn get_admin(env: Env) -> Address {
-        storage::get_admin(&env)
+    /// Check the parameters of a governance action
+    fn validate_action(action: &GovAction) -> Result<(), CreditLineError> {
+        if let GovAction::SetSigners(signers, threshold) = action {
+            if !governance::is_valid_signer_set(signers, *threshold) {
+                return Err(CreditLineError::InvalidSignerSet);
+            }
+        }
+        Ok(())
     }
 
-    /// Set the reputation contract address (admin only)
-    pub fn set_reputation_contract(env: Env, admin: Address, address: Address) {
-        admin.require_auth();
-        access::require_admin(&env, &admin);
+    /// Run a proposal's action if it has enough approvals, returns true when it ran
+    fn execute_if_approved(
+        env: &Env,
+        proposal_id: u64,
+        proposal: &mut Proposal,
+    ) -> Result<bool, CreditLineError> {
+        if !governance::is_approved(env, proposal) {
+            return Ok(false);
+        }
+
+        Self::validate_action(&proposal.action)?;
+        governance::mark_executed(env, proposal_id, proposal);
 
-        storage::set_reputation_contract(&env, &address);
+        let contract = env.current_contract_address();
+        match proposal.action.clone() {
+            GovAction::SetAdmin(new_admin) => {
+                let old_admin = storage::get_admin(env);
+                storage::set_admin(env, &new_admin);
+                storage::remove_pending_admin(env);
+                events::emit_admin_changed(env, &old_admin, &new_admin);
+            }
+            GovAction::GrantRole(role, account) => {
+                roles::grant_role(env, &contract, role, &account)
+            }
+            GovAction::RevokeRole(role, account) => {
+                roles::revoke_role(env, &contract, role, &account)
+            }
+            GovAction::SetSigners(signers, threshold) => {
+                governance::set_signers(env, &signers, threshold)
+            }
+            GovAction::Upgrade(new_wasm_hash) => upgrade::upgrade(env, &new_wasm_hash),
+        }
+        Ok(true)
     }
 
-    /// Set the merchant registry contract address (admin only)
-    pub fn set_merchant_registry(env: Env, admin: Address, address: Address) {
-        admin.require_auth();
-        access::require_admin(&env, &admin);
+    /// Require the role needed to queue, execute or cancel a change
+    /// External contract addresses and the timelock delay are admin only
+    fn require_change_role(env: &Env, caller: &Address, change: &ParamChange) {
+        match change {
+            ParamChange::ReputationContract(_)
+            | ParamChange::MerchantRegistry(_)
+            | ParamChange::LiquidityPool(_)
+            | ParamChange::Token(_)
+            | ParamChange::TimelockDelay(_) => access::require_admin(env, caller),
+            _ => access::require_role(env, Role::RiskManager, caller),
+        }
+    }
+
+    /// Check the values of a parameter change
+    fn validate_change(env: &Env, change: &ParamChange) -> Result<(), CreditLineError> {
+        match change {
+            ParamChange::Token(_) | ParamChange::LiquidityPool(_) => {
+                // Open loans are held in the current token and tracked by the current pool
+                if storage::get_active_loan_count(env) > 0 {
+                    return Err(CreditLineError::LoansOutstanding);
+                }
+            }
+            ParamChange::CreditTiers(tiers) => {
+                if tiers.is_empty() {
+                    return Err(CreditLineError::InvalidTierConfig);
+                }
 
-        storage::set_merchant_registry(&env, &address);
+                // Tiers must be ordered by strictly decreasing `min_score`, ending with a tier at score 0
+                let mut previous_min: Option<u32> = None;
+                for config in tiers.iter() {
+                    if config.apr_bps > types::MAX_APR_BPS || config.max_credit < 0 {
+                        return Err(CreditLineError::InvalidTierConfig);
+                    }
+                    if previous_min.is_some_and(|min| config.min_score >= min) {
+                        return Err(CreditLineError::InvalidTierConfig);
+                    }
+                    previous_min = Some(config.min_score);
+                }
+
+                if previous_min != Some(0) {
+                    return Err(CreditLineError::InvalidTierConfig);
+                }
+            }
+            ParamChange::ExposureLimits(limits) | ParamChange::TierExposureLimits(_, limits) => {
+                if limits.max_active_loans == 0 || limits.max_outstanding <= 0 {
+                    return Err(CreditLineError::InvalidExposureLimits);
+                }
+            }
+            ParamChange::LateFeeConfig(config) => {
+                if config.flat_fee < 0 || config.percent_bps as i128 > types::BPS_DENOMINATOR {
+                    return Err(CreditLineError::InvalidLateFeeConfig);
+                }
+            }
+            ParamChange::ScheduleLimits(limits) => {
+                if limits.min_installments == 0
+                    || limits.min_installments > limits.max_installments
+                    || limits.min_tenor > limits.max_tenor
+                {
+                    return Err(CreditLineError::InvalidScheduleLimits);
+                }
+            }
+            ParamChange::PayoffRebate(rebate_bps) => {
+                if *rebate_bps as i128 > types::BPS_DENOMINATOR {
+                    return Err(CreditLineError::InvalidRebate);
+                }
+            }
+            ParamChange::TimelockDelay(delay) => {
+                if *delay > types::MAX_TIMELOCK_DELAY {
+                    return Err(CreditLineError::InvalidTimelockDelay);
+                }
+            }
+            ParamChange::ReputationContract(_)
+            | ParamChange::MerchantRegistry(_)
+            | ParamChange::ClearExposureLimits(_) => {}
+        }
+        Ok(())
     }
 
-    /// Set the liquidity pool contract address (admin only)
-    pub fn set_liquidity_pool(env: Env, admin: Address, address: Address) {
-        admin.require_auth();
-        access::require_admin(&env, &admin);
+    /// Write a validated parameter change to storage
+    fn apply_change(env: &Env, change: &ParamChange) {
+        match change {
+            ParamChange::ReputationContract(address) => {
+                storage::set_reputation_contract(env, address)
+            }
+            ParamChange::MerchantRegistry(address) => storage::set_merchant_registry(env, address),
+            ParamChange::LiquidityPool(address) => storage::set_liquidity_pool(env, address),
+            ParamChange::Token(address) => storage::set_token(env, address),
+            ParamChange::CreditTiers(tiers) => storage::set_credit_tiers(env, tiers),
+            ParamChange::TierExposureLimits(tier, limits) => {
+                let mut overrides = storage::get_tier_exposure_limits(env);
+                overrides.set(*tier, limits.clone());
+                storage::set_tier_exposure_limits(env, &overrides);
+            }
+            ParamChange::ExposureLimits(limits) => storage::set_exposure_limits(env, limits),
+            ParamChange::ClearExposureLimits(tier) => {
+                let mut overrides = storage::get_tier_exposure_limits(env);
+                overrides.remove(*tier);
+                storage::set_tier_exposure_limits(env, &overrides);
+            }
+            ParamChange::LateFeeConfig(config) => storage::set_late_fee_config(env, config),
+            ParamChange::ScheduleLimits(limits) => storage::set_schedule_limits(env, limits),
+            ParamChange::PayoffRebate(rebate_bps) => storage::set_payoff_rebate(env, *rebate_bps),
+            ParamChange::TimelockDelay(delay) => storage::set_timelock_delay(env, *delay),
+        }
+    }
+
+    /// Spread the total interest evenly so every installment but the last is the same amount
+    /// The last installment absorbs the rounding remainders of principal and interest
+    fn equalize_installments(
+        env: &Env,
+        schedule: &Vec<RepaymentInstallment>,
+        interest_amount: i128,
+    ) -> Vec<RepaymentInstallment> {
+        let count = schedule.len();
+        let mut equalized = Vec::new(env);
+        if count == 0 {
+            return equalized;
+        }
+
+        let portion = interest_amount / count as i128;
+        let remainder = interest_amount - portion * count as i128;
+
+        for (index, installment) in schedule.iter().enumerate() {
+            let interest = if index as u32 == count - 1 {
+                portion + remainder
+            } else {
+                portion
+            };
+
+            equalized.push_back(RepaymentInstallment {
+                amount: installment.principal + interest,
+                interest,
+                ..installment
+            });
+        }
 
-        storage::set_liquidity_pool(&env, &address);
+        equalized
     }
 
-    /// Validate guarantee amount is at least 20% of total amount
-    fn validate_guarantee(env: &Env, total_amount: i128, guarantee_amount: i128) {
-        if total_amount <= 0 || guarantee_amount <= 0 {
-            panic_with_error!(env, CreditLineError::InvalidAmount);
+    /// Validate the repayment schedule against the configured limits
+    /// Due dates must be in the future and strictly increasing, and the principal
+    /// portions must add up to the financed amount. Interest is left out on purpose:
+    /// `price_schedule` adds it from the tier APR, so priced schedules always total
+    /// principal plus interest
+    fn validate_schedule(env: &Env, principal: i128, schedule: &Vec<RepaymentInstallment>) {
+        if Self::validate_installments(env, schedule) != principal {
+            panic_with_error!(env, CreditLineError::ScheduleAmountMismatch);
         }
+    }
 
-        // Calculate minimum guarantee (20% of total)
-        let min_guarantee = total_amount
-            .checked_mul(types::MIN_GUARANTEE_PERCENT)
-            .and_then(|v| v.checked_div(100))
-            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+    /// Validate installment count, due dates and tenor, returning the sum of the amounts
+    fn validate_installments(env: &Env, schedule: &Vec<RepaymentInstallment>) -> i128 {
+        let limits = storage::get_schedule_limits(env);
 
-        if guarantee_amount < min_guarantee {
-            panic_with_error!(env, CreditLineError::InsufficientGuarantee);
+        if schedule.len() < limits.min_installments || schedule.len() > limits.max_installments {
+            panic_with_error!(env, CreditLineError::InvalidInstallmentCount);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut previous_due_date = now;
+        let mut total: i128 = 0;
+        for installment in schedule.iter() {
+            if installment.amount <= 0 {
+                panic_with_error!(env, CreditLineError::InvalidAmount);
+            }
+            if installment.due_date <= now {
+                panic_with_error!(env, CreditLineError::DueDateInPast);
+            }
+            if installment.due_date <= previous_due_date {
+                panic_with_error!(env, CreditLineError::DueDatesNotIncreasing);
+            }
+            previous_due_date = installment.due_date;
+
+            total = total
+                .checked_add(installment.amount)
+                .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Overflow));
+        }
+
+        let tenor = previous_due_date - now;
+        if tenor < limits.min_tenor || tenor > limits.max_tenor {
+            panic_with_error!(env, CreditLineError::InvalidTenor);
         }
+
+        total
     }
 
-    /// Validate merchant is registered and active
-    /// TODO: Implement when Merchant Registry contract is available (Phase 5)
+    /// Validate merchant is registered and active in the Merchant Registry
     fn validate_merchant(env: &Env, merchant: &Address) {
-        let merchant_registry = storage::get_merchant_registry(env);
+        let merchant_registry = storage::get_merchant_registry(env)
+            .unwrap_or_else(|| panic!("Merchant registry not configured"));
 
-        if merchant_registry.is_none() {
-            // Merchant registry not configured yet
-            // For now, we'll skip this validation
-            // TODO: Remove this when merchant registry is implemented
-            return;
+        let is_active = MerchantRegistryClient::new(env, &merchant_registry).is_active(merchant);
+
+        if !is_active {
+            panic_with_error!(env, CreditLineError::MerchantNotActive);
         }
+    }
+
+    /// Get the address the Merchant Registry pays a merchant's loans out to
+    fn merchant_payout_address(env: &Env, merchant: &Address) -> Address {
+        let merchant_registry = storage::get_merchant_registry(env)
+            .unwrap_or_else(|| panic!("Merchant registry not configured"));
 
-        // TODO: Query merchant registry contract
-        // Example: merchant_registry_client.is_active(merchant)
-        // For now, we assume merchant is valid
-        let _ = merchant;
+        MerchantRegistryClient::new(env, &merchant_registry).get_payout_address(merchant)
     }
 
-    /// Validate user has sufficient reputation
-    fn validate_reputation(env: &Env, user: &Address) {
+    /// Validate user has sufficient reputation and that the financed amount fits their tier
+    /// Returns the tier the loan is priced at
+    fn validate_reputation(env: &Env, user: &Address, amount_financed: i128) -> TierConfig {
         let reputation_contract = storage::get_reputation_contract(env)
             .unwrap_or_else(|| panic!("Reputation contract not configured"));
 
         // Call the reputation contract to get user's score
-        // Using the reputation contract interface
-        use soroban_sdk::IntoVal;
-
-        let score: u32 = env.invoke_contract(
-            &reputation_contract,
-            &symbol_short!("get_score"),
-            (user,).into_val(env),
-        );
+        let score = ReputationClient::new(env, &reputation_contract).get_score(user);
 
         if score < types::MIN_REPUTATION_THRESHOLD {
             panic_with_error!(env, CreditLineError::InsufficientReputation);
         }
+
+        let tier = Self::tier_for_score(env, score);
+        if amount_financed > tier.max_credit {
+            panic_with_error!(env, CreditLineError::CreditLimitExceeded);
+        }
+
+        tier
+    }
+
+    /// Find the first tier whose minimum score the given score reaches
+    fn tier_for_score(env: &Env, score: u32) -> TierConfig {
+        storage::get_credit_tiers(env)
+            .iter()
+            .find(|config| score >= config.min_score)
+            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::InvalidTierConfig))
     }
 
-    /// Validate liquidity pool has sufficient funds
-    /// TODO: Implement when Liquidity Pool contract is available (Phase 6)
-    fn validate_liquidity(env: &Env, total_amount: i128, guarantee_amount: i128) {
-        let liquidity_pool = storage::get_liquidity_pool(env);
+    /// Validate liquidity pool has sufficient funds to finance the principal
+    fn validate_liquidity(env: &Env, principal: i128) {
+        let available = Self::liquidity_pool(env).get_available_liquidity();
 
-        if liquidity_pool.is_none() {
-            // Liquidity pool not configured yet
-            // For now, we'll skip this validation
-            // TODO: Remove this when liquidity pool is implemented
-            return;
+        if available < principal {
+            panic_with_error!(env, CreditLineError::InsufficientLiquidity);
         }
+    }
 
-        // The loan requires (total_amount - guarantee_amount) from the pool
-        let required_from_pool = total_amount
-            .checked_sub(guarantee_amount)
-            .unwrap_or_else(|| panic_with_error!(env, CreditLineError::Underflow));
+    /// Get a client for the configured Liquidity Pool
+    fn liquidity_pool(env: &Env) -> LiquidityPoolClient<'_> {
+        let liquidity_pool = storage::get_liquidity_pool(env)
+            .unwrap_or_else(|| panic!("Liquidity pool not configured"));
+        LiquidityPoolClient::new(env, &liquidity_pool)
+    }
+
+    /// Amount needed to settle a loan now and the unaccrued interest rebated
+    fn payoff_quote(env: &Env, loan: &Loan) -> Result<(LoanPayoff, i128), CreditLineError> {
+        let principal = loan
+            .principal
+            .checked_sub(loan.principal_paid)
+            .ok_or(CreditLineError::Underflow)?;
+
+        // Paying an installment ahead of time also pays its interest ahead of time
+        let accrued = Self::accrued_interest(loan, env.ledger().timestamp())?;
+        let accrued_unpaid = accrued
+            .checked_sub(loan.interest_paid)
+            .ok_or(CreditLineError::Underflow)?
+            .max(0);
+
+        let unaccrued = loan
+            .interest_amount
+            .checked_sub(loan.interest_paid)
+            .and_then(|v| v.checked_sub(accrued_unpaid))
+            .ok_or(CreditLineError::Underflow)?
+            .max(0);
+        let rebate = unaccrued
+            .checked_mul(storage::get_payoff_rebate(env) as i128)
+            .map(|v| v / types::BPS_DENOMINATOR)
+            .ok_or(CreditLineError::Overflow)?;
+
+        let interest = accrued_unpaid
+            .checked_add(unaccrued - rebate)
+            .ok_or(CreditLineError::Overflow)?;
+        let total = principal
+            .checked_add(interest)
+            .and_then(|v| v.checked_add(loan.fees))
+            .ok_or(CreditLineError::Overflow)?;
+
+        Ok((
+            LoanPayoff {
+                principal,
+                interest,
+                fees: loan.fees,
+                total,
+            },
+            rebate,
+        ))
+    }
+
+    /// Principal and interest still owed on the repayment schedule, fees excluded
+    fn scheduled_balance(loan: &Loan) -> Result<i128, CreditLineError> {
+        loan.remaining_balance
+            .checked_sub(loan.fees)
+            .ok_or(CreditLineError::Underflow)
+    }
+
+    /// `value * amount / base`, rounded down
+    fn share_of(value: i128, amount: i128, base: i128) -> Result<i128, CreditLineError> {
+        value
+            .checked_mul(amount)
+            .map(|v| v / base)
+            .ok_or(CreditLineError::Overflow)
+    }
+
+    /// Principal still owed to the pool
+    fn outstanding_principal(loan: &Loan) -> i128 {
+        loan.principal - loan.principal_paid
+    }
+
+    /// Simple interest on a principal amount over the elapsed seconds
+    fn interest_for(principal: i128, apr_bps: u32, elapsed: u64) -> Result<i128, CreditLineError> {
+        principal
+            .checked_mul(apr_bps as i128)
+            .and_then(|v| v.checked_mul(elapsed as i128))
+            .and_then(|v| v.checked_div(types::SECONDS_PER_YEAR as i128 * types::BPS_DENOMINATOR))
+            .ok_or(CreditLineError::Overflow)
+    }
+
+    /// Interest accrued on every installment up to the given time, capped at its due date
+    fn accrued_interest(loan: &Loan, now: u64) -> Result<i128, CreditLineError> {
+        if let Some(restructured_at) = loan.restructured_at {
+            // Interest added by a restructure accrues evenly until the last due date
+            let end = loan
+                .repayment_schedule
+                .iter()
+                .map(|installment| installment.due_date)
+                .max()
+                .unwrap_or(restructured_at);
+            let term = end.saturating_sub(restructured_at);
+            let elapsed = now.min(end).saturating_sub(restructured_at);
+            let added = loan
+                .interest_amount
+                .checked_sub(loan.capitalized_interest)
+                .ok_or(CreditLineError::Underflow)?;
+            let accrued = if term == 0 {
+                added
+            } else {
+                Self::share_of(added, elapsed as i128, term as i128)?
+            };
+            return loan
+                .capitalized_interest
+                .checked_add(accrued)
+                .ok_or(CreditLineError::Overflow);
+        }
 
-        // TODO: Query liquidity pool contract
-        // Example: liquidity_pool_client.get_available_liquidity()
-        // For now, we assume liquidity is sufficient
-        let _ = required_from_pool;
+        let mut accrued: i128 = 0;
+        for installment in loan.repayment_schedule.iter() {
+            let until = now.min(installment.due_date);
+            let interest = Self::interest_for(
+                installment.principal,
+                loan.apr_bps,
+                until.saturating_sub(loan.created_at),
+            )?;
+            accrued = accrued
+                .checked_add(interest)
+                .ok_or(CreditLineError::Overflow)?;
+        }
+        Ok(accrued)
     }
 
-    pub fn mark_defaulted(env: Env, loan_id: u64) -> Result<(), CreditLineError> {
+    /// Mark an overdue loan as defaulted, forfeiting its guarantee to the pool (collector only)
+    pub fn mark_defaulted(
+        env: Env,
+        collector: Address,
+        loan_id: u64,
+    ) -> Result<(), CreditLineError> {
+        collector.require_auth();
+        access::require_role(&env, Role::Collector, &collector);
+        access::require_not_paused(&env, types::PAUSE_COLLECTION);
         // 1. Validation: Loan must exist
         let mut loan = storage::read_loan(&env, loan_id).ok_or(CreditLineError::LoanNotFound)?;
 
@@ -240,14 +2048,31 @@ impl CreditLineContract {
         }
 
         // 4. Transfer guarantee to Liquidity Pool
-        let _lp_address =
+        let lp_address =
             storage::get_liquidity_pool(&env).ok_or(CreditLineError::InsufficientLiquidity)?;
+        let pool = LiquidityPoolClient::new(&env, &lp_address);
+
+        token::Client::new(&env, &Self::token_address(&env)).transfer(
+            &env.current_contract_address(),
+            &pool.address,
+            &loan.guarantee_amount,
+        );
 
-        // Note: In a real Soroban contract, you'd use a token client to move funds here.
-        // For Phase 3, we logic-gate the state change.
+        // Write off the unpaid principal, offset by the forfeited guarantee
+        pool.record_default(
+            &env.current_contract_address(),
+            &Self::outstanding_principal(&loan),
+            &loan.guarantee_amount,
+        );
 
         // 5. Update Status
         loan.status = LoanStatus::Defaulted;
+        Self::reduce_exposure(
+            &env,
+            &loan.borrower,
+            Self::outstanding_principal(&loan),
+            true,
+        );
         storage::write_loan(&env, &loan);
 
         // 6. Emit Event
@@ -260,16 +2085,8 @@ impl CreditLineContract {
             loan.guarantee_amount,
         );
 
-        // 7. Trigger reputation decrease (Phase 4 placeholder)
-        if let Some(reputation_contract) = storage::get_reputation_contract(&env) {
-            // Only attempt the call if we aren't in a test or if you've set up a mock
-            // For now, let's just make sure the call is reachable
-            env.invoke_contract::<()>(
-                &reputation_contract,
-                &symbol_short!("slash"),
-                (loan.borrower,).into_val(&env),
-            );
-        }
+        // 7. Trigger reputation decrease
+        Self::update_reputation(&env, &loan.borrower, ReputationHook::Slash);
 
         Ok(())
     }
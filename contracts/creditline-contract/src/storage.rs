@@ -1,14 +1,31 @@
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
+use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
-use crate::types::Loan;
+use crate::types::{
+    self, BorrowerExposure, CreditTier, DataKey, ExposureLimits, LateFeeConfig, LegacyLoan, Loan,
+    LoanIndex, QueuedChange, RestructureProposal, ScheduleLimits, ScheduleRevision, TierConfig,
+    LOAN_TTL_EXTEND_TO, LOAN_TTL_THRESHOLD,
+};
 
 // Storage keys
-pub const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+pub use common::ADMIN_KEY;
+pub const PENDING_ADMIN: Symbol = symbol_short!("PENDADMIN");
 pub const LOAN_COUNTER: Symbol = symbol_short!("LOANCNT");
 pub const LOANS_MAP: Symbol = symbol_short!("LOANS");
 pub const REPUTATION_CONTRACT: Symbol = symbol_short!("REPCONT");
 pub const MERCHANT_REGISTRY: Symbol = symbol_short!("MERCHANT");
 pub const LIQUIDITY_POOL: Symbol = symbol_short!("LIQPOOL");
+pub const TOKEN: Symbol = symbol_short!("TOKEN");
+pub const ACTIVE_LOANS: Symbol = symbol_short!("ACTLOANS");
+pub const CREDIT_TIERS: Symbol = symbol_short!("TIERS");
+pub const LATE_FEES: Symbol = symbol_short!("LATEFEES");
+pub const SCHEDULE_LIMITS: Symbol = symbol_short!("SCHEDLIM");
+pub const EXPOSURE_LIMITS: Symbol = symbol_short!("EXPLIMIT");
+pub const TIER_EXPOSURE_LIMITS: Symbol = symbol_short!("TIERLIMIT");
+pub const PAYOFF_REBATE: Symbol = symbol_short!("REBATEBPS");
+pub const PAUSED: Symbol = symbol_short!("PAUSED");
+pub const CHANGE_COUNTER: Symbol = symbol_short!("CHANGECNT");
+pub const RESTRUCTURE_COUNTER: Symbol = symbol_short!("RSTRCNT");
+pub const TIMELOCK_DELAY: Symbol = symbol_short!("TLDELAY");
 
 /// Get the admin address from storage
 pub fn get_admin(env: &Env) -> Address {
@@ -23,6 +40,21 @@ pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&ADMIN_KEY, admin);
 }
 
+/// Get the address proposed as the next admin, if any
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&PENDING_ADMIN)
+}
+
+/// Set the address proposed as the next admin
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&PENDING_ADMIN, admin);
+}
+
+/// Clear the proposed admin
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().instance().remove(&PENDING_ADMIN);
+}
+
 /// Get the current loan counter (for generating unique loan IDs)
 pub fn get_loan_counter(env: &Env) -> u64 {
     env.storage().instance().get(&LOAN_COUNTER).unwrap_or(0)
@@ -36,27 +68,169 @@ pub fn increment_loan_counter(env: &Env) -> u64 {
     next
 }
 
-/// Read a loan from storage
+/// Read a loan from storage, extending its TTL when found
+/// Falls back to the legacy instance map for loans not migrated yet
 pub fn read_loan(env: &Env, loan_id: u64) -> Option<Loan> {
-    let loans: Map<u64, Loan> = env
-        .storage()
-        .instance()
-        .get(&LOANS_MAP)
-        .unwrap_or_else(|| Map::new(env));
+    let key = DataKey::Loan(loan_id);
+    let loan: Option<Loan> = env.storage().persistent().get(&key);
 
-    loans.get(loan_id)
+    if loan.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+        return loan;
+    }
+
+    read_legacy_loans(env)
+        .and_then(|loans| loans.get(loan_id))
+        .map(|legacy| legacy.into_loan(env))
 }
 
 /// Write a loan to storage
 pub fn write_loan(env: &Env, loan: &Loan) {
-    let mut loans: Map<u64, Loan> = env
-        .storage()
+    let key = DataKey::Loan(loan.loan_id);
+    env.storage().persistent().set(&key, loan);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+}
+
+/// Storage keys of an index's length and of one of its slots
+fn loan_index_keys(index: &LoanIndex, slot: u32) -> (DataKey, DataKey) {
+    match index {
+        LoanIndex::Borrower(borrower) => (
+            DataKey::BorrowerLoanCount(borrower.clone()),
+            DataKey::BorrowerLoan(borrower.clone(), slot),
+        ),
+        LoanIndex::Merchant(merchant) => (
+            DataKey::MerchantLoanCount(merchant.clone()),
+            DataKey::MerchantLoan(merchant.clone(), slot),
+        ),
+    }
+}
+
+/// Get the number of loan IDs in an index
+pub fn get_loan_index_len(env: &Env, index: &LoanIndex) -> u32 {
+    let (key, _) = loan_index_keys(index, 0);
+    let len: Option<u32> = env.storage().persistent().get(&key);
+
+    match len {
+        Some(len) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+            len
+        }
+        None => 0,
+    }
+}
+
+/// Read the loan ID stored in one slot of an index
+pub fn read_indexed_loan(env: &Env, index: &LoanIndex, slot: u32) -> Option<u64> {
+    let (_, key) = loan_index_keys(index, slot);
+    let loan_id: Option<u64> = env.storage().persistent().get(&key);
+
+    if loan_id.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+    }
+    loan_id
+}
+
+/// Append a loan ID to the borrower and merchant indexes
+/// Each ID gets its own slot entry, so appending never rewrites earlier IDs
+pub fn index_loan(env: &Env, loan: &Loan) {
+    for index in [
+        LoanIndex::Borrower(loan.borrower.clone()),
+        LoanIndex::Merchant(loan.merchant.clone()),
+    ] {
+        let len = get_loan_index_len(env, &index);
+        let (len_key, slot_key) = loan_index_keys(&index, len);
+
+        env.storage().persistent().set(&slot_key, &loan.loan_id);
+        env.storage().persistent().set(&len_key, &(len + 1));
+        for key in [&slot_key, &len_key] {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+        }
+    }
+}
+
+/// Get the number of loans still active across all borrowers
+pub fn get_active_loan_count(env: &Env) -> u32 {
+    env.storage().instance().get(&ACTIVE_LOANS).unwrap_or(0)
+}
+
+/// Set the number of loans still active across all borrowers
+pub fn set_active_loan_count(env: &Env, count: u32) {
+    env.storage().instance().set(&ACTIVE_LOANS, &count);
+}
+
+/// Get a borrower's open loans and unpaid principal
+pub fn get_exposure(env: &Env, borrower: &Address) -> BorrowerExposure {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Exposure(borrower.clone()))
+        .unwrap_or_default()
+}
+
+/// Set a borrower's open loans and unpaid principal
+pub fn set_exposure(env: &Env, borrower: &Address, exposure: &BorrowerExposure) {
+    let key = DataKey::Exposure(borrower.clone());
+    env.storage().persistent().set(&key, exposure);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+}
+
+/// Get the exposure limits that apply to every tier without an override
+pub fn get_exposure_limits(env: &Env) -> ExposureLimits {
+    env.storage()
         .instance()
-        .get(&LOANS_MAP)
-        .unwrap_or_else(|| Map::new(env));
+        .get(&EXPOSURE_LIMITS)
+        .unwrap_or(ExposureLimits {
+            max_active_loans: types::DEFAULT_MAX_ACTIVE_LOANS,
+            max_outstanding: types::DEFAULT_MAX_OUTSTANDING,
+        })
+}
 
-    loans.set(loan.loan_id, loan.clone());
-    env.storage().instance().set(&LOANS_MAP, &loans);
+/// Set the exposure limits that apply to every tier without an override
+pub fn set_exposure_limits(env: &Env, limits: &ExposureLimits) {
+    env.storage().instance().set(&EXPOSURE_LIMITS, limits);
+}
+
+/// Get the per-tier exposure limit overrides
+pub fn get_tier_exposure_limits(env: &Env) -> Map<CreditTier, ExposureLimits> {
+    env.storage()
+        .instance()
+        .get(&TIER_EXPOSURE_LIMITS)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Set the per-tier exposure limit overrides
+pub fn set_tier_exposure_limits(env: &Env, limits: &Map<CreditTier, ExposureLimits>) {
+    env.storage().instance().set(&TIER_EXPOSURE_LIMITS, limits);
+}
+
+/// Check whether a loan is stored under its own key
+pub fn has_loan_entry(env: &Env, loan_id: u64) -> bool {
+    env.storage().persistent().has(&DataKey::Loan(loan_id))
+}
+
+/// Read the legacy map that held every loan in instance storage, in the first release layout
+pub fn read_legacy_loans(env: &Env) -> Option<Map<u64, LegacyLoan>> {
+    env.storage().instance().get(&LOANS_MAP)
+}
+
+/// Write back the legacy loan map, removing it once empty
+pub fn write_legacy_loans(env: &Env, loans: &Map<u64, LegacyLoan>) {
+    if loans.is_empty() {
+        env.storage().instance().remove(&LOANS_MAP);
+    } else {
+        env.storage().instance().set(&LOANS_MAP, loans);
+    }
 }
 
 /// Get the Reputation Contract address
@@ -88,3 +262,184 @@ pub fn get_liquidity_pool(env: &Env) -> Option<Address> {
 pub fn set_liquidity_pool(env: &Env, address: &Address) {
     env.storage().instance().set(&LIQUIDITY_POOL, address);
 }
+
+/// Get the stablecoin token address used for repayments
+pub fn get_token(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&TOKEN)
+}
+
+/// Set the stablecoin token address used for repayments
+pub fn set_token(env: &Env, address: &Address) {
+    env.storage().instance().set(&TOKEN, address);
+}
+
+/// Get the credit tier table, falling back to the defaults
+pub fn get_credit_tiers(env: &Env) -> Vec<TierConfig> {
+    env.storage()
+        .instance()
+        .get(&CREDIT_TIERS)
+        .unwrap_or_else(|| types::default_credit_tiers(env))
+}
+
+/// Set the credit tier table
+pub fn set_credit_tiers(env: &Env, tiers: &Vec<TierConfig>) {
+    env.storage().instance().set(&CREDIT_TIERS, tiers);
+}
+
+/// Get the late fee configuration, falling back to the defaults
+pub fn get_late_fee_config(env: &Env) -> LateFeeConfig {
+    env.storage()
+        .instance()
+        .get(&LATE_FEES)
+        .unwrap_or(LateFeeConfig {
+            grace_period: types::DEFAULT_GRACE_PERIOD,
+            flat_fee: types::DEFAULT_LATE_FEE,
+            percent_bps: types::DEFAULT_LATE_FEE_BPS,
+        })
+}
+
+/// Set the late fee configuration
+pub fn set_late_fee_config(env: &Env, config: &LateFeeConfig) {
+    env.storage().instance().set(&LATE_FEES, config);
+}
+
+/// Get the repayment schedule limits, falling back to the defaults
+pub fn get_schedule_limits(env: &Env) -> ScheduleLimits {
+    env.storage()
+        .instance()
+        .get(&SCHEDULE_LIMITS)
+        .unwrap_or(ScheduleLimits {
+            min_installments: types::DEFAULT_MIN_INSTALLMENTS,
+            max_installments: types::DEFAULT_MAX_INSTALLMENTS,
+            min_tenor: types::DEFAULT_MIN_TENOR,
+            max_tenor: types::DEFAULT_MAX_TENOR,
+        })
+}
+
+/// Set the repayment schedule limits
+pub fn set_schedule_limits(env: &Env, limits: &ScheduleLimits) {
+    env.storage().instance().set(&SCHEDULE_LIMITS, limits);
+}
+
+/// Get the share of unaccrued interest waived on early payoff, in basis points
+pub fn get_payoff_rebate(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&PAYOFF_REBATE)
+        .unwrap_or(types::DEFAULT_PAYOFF_REBATE_BPS)
+}
+
+/// Set the share of unaccrued interest waived on early payoff, in basis points
+pub fn set_payoff_rebate(env: &Env, rebate_bps: u32) {
+    env.storage().instance().set(&PAYOFF_REBATE, &rebate_bps);
+}
+
+/// Get the pause flags currently set
+pub fn get_paused(env: &Env) -> u32 {
+    env.storage().instance().get(&PAUSED).unwrap_or(0)
+}
+
+/// Set the pause flags
+pub fn set_paused(env: &Env, flags: u32) {
+    env.storage().instance().set(&PAUSED, &flags);
+}
+
+/// Increment and return the next restructure proposal ID
+pub fn increment_restructure_counter(env: &Env) -> u64 {
+    let current: u64 = env
+        .storage()
+        .instance()
+        .get(&RESTRUCTURE_COUNTER)
+        .unwrap_or(0);
+    let next = current
+        .checked_add(1)
+        .expect("Restructure counter overflow");
+    env.storage().instance().set(&RESTRUCTURE_COUNTER, &next);
+    next
+}
+
+/// Get the pending restructure proposal for a loan
+pub fn get_restructure(env: &Env, loan_id: u64) -> Option<RestructureProposal> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Restructure(loan_id))
+}
+
+/// Store the pending restructure proposal for a loan
+pub fn set_restructure(env: &Env, loan_id: u64, proposal: &RestructureProposal) {
+    let key = DataKey::Restructure(loan_id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+}
+
+/// Remove the pending restructure proposal for a loan
+pub fn remove_restructure(env: &Env, loan_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Restructure(loan_id));
+}
+
+/// Get the schedules a loan has had before its current one, oldest first
+pub fn get_schedule_history(env: &Env, loan_id: u64) -> Vec<ScheduleRevision> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ScheduleHistory(loan_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Append a replaced schedule to a loan's history
+pub fn push_schedule_history(env: &Env, loan_id: u64, revision: &ScheduleRevision) {
+    let key = DataKey::ScheduleHistory(loan_id);
+    let mut history = get_schedule_history(env, loan_id);
+    history.push_back(revision.clone());
+    env.storage().persistent().set(&key, &history);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+}
+
+/// Increment and return the next queued change ID
+pub fn increment_change_counter(env: &Env) -> u64 {
+    let current: u64 = env.storage().instance().get(&CHANGE_COUNTER).unwrap_or(0);
+    let next = current.checked_add(1).expect("Change counter overflow");
+    env.storage().instance().set(&CHANGE_COUNTER, &next);
+    next
+}
+
+/// Get a queued parameter change
+pub fn get_queued_change(env: &Env, change_id: u64) -> Option<QueuedChange> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QueuedChange(change_id))
+}
+
+/// Store a queued parameter change
+pub fn set_queued_change(env: &Env, change_id: u64, queued: &QueuedChange) {
+    let key = DataKey::QueuedChange(change_id);
+    env.storage().persistent().set(&key, queued);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LOAN_TTL_THRESHOLD, LOAN_TTL_EXTEND_TO);
+}
+
+/// Remove an executed or cancelled parameter change
+pub fn remove_queued_change(env: &Env, change_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::QueuedChange(change_id));
+}
+
+/// Get the delay between queueing and executing a parameter change
+pub fn get_timelock_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&TIMELOCK_DELAY)
+        .unwrap_or(types::DEFAULT_TIMELOCK_DELAY)
+}
+
+/// Set the delay between queueing and executing a parameter change
+pub fn set_timelock_delay(env: &Env, delay: u64) {
+    env.storage().instance().set(&TIMELOCK_DELAY, &delay);
+}
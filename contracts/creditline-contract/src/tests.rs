@@ -1,24 +1,159 @@
 #![cfg(test)]
-
-use crate::{CreditLineContract, CreditLineContractClient, LoanStatus, RepaymentInstallment};
+extern crate std;
+
+use crate::{storage, types::DataKey};
+use crate::{
+    BorrowerExposure, CreditLineContract, CreditLineContractClient, CreditLineError, CreditTier,
+    ExposureLimits, GovAction, LateFeeConfig, LoanPayoff, LoanRefund, LoanStatus, ParamChange,
+    RepaymentInstallment, Role, ScheduleLimits, ScheduleRevision, TierConfig, PAUSE_ALL,
+    PAUSE_ORIGINATION, PAUSE_REPAYMENT,
+};
+use liquidity_pool_contract::{LiquidityPoolContract, LiquidityPoolContractClient};
+use merchant_registry_contract::{MerchantRegistryContract, MerchantRegistryContractClient};
+use reputation_contract::{ReputationContract, ReputationContractClient};
 use soroban_sdk::{
-    contract, contractimpl,
-    testutils::{Address as _, Ledger},
-    Address, Env,
+    symbol_short,
+    testutils::{
+        storage::Persistent as _, Address as _, AuthorizedFunction, AuthorizedInvocation, Events,
+        Ledger,
+    },
+    token, vec, Address, BytesN, Env, IntoVal, Map, String, Symbol,
 };
 
-// NOTE: Integration tests with reputation contract are skipped for now
-// They will be added when all contracts are implemented and properly configured
-#[contract]
-pub struct MockReputation;
+/// Registers a Stellar Asset Contract and returns its address with an admin client for minting
+fn create_token<'a>(env: &Env, admin: &Address) -> (Address, token::StellarAssetClient<'a>) {
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let address = sac.address();
+    (
+        address.clone(),
+        token::StellarAssetClient::new(env, &address),
+    )
+}
+
+/// Registers a Merchant Registry with `merchant` as an active merchant
+fn create_merchant_registry(env: &Env, admin: &Address, merchant: &Address) -> Address {
+    let registry_id = env.register(MerchantRegistryContract, ());
+    let registry = MerchantRegistryContractClient::new(env, &registry_id);
+
+    registry.initialize(admin, &deployed_wasm_hash(env));
+    registry.register_merchant(
+        admin,
+        merchant,
+        &String::from_str(env, "Test Merchant"),
+        &symbol_short!("retail"),
+        merchant,
+    );
+
+    registry_id
+}
+
+/// Fully wired CreditLine with token, merchant registry and a funded liquidity pool
+struct LoanTest<'a> {
+    client: CreditLineContractClient<'a>,
+    contract_id: Address,
+    admin: Address,
+    user: Address,
+    merchant: Address,
+    registry_id: Address,
+    pool: LiquidityPoolContractClient<'a>,
+    token: token::Client<'a>,
+    reputation: ReputationContractClient<'a>,
+}
+
+const POOL_LIQUIDITY: i128 = 10_000;
+
+fn setup_loan_test(env: &Env) -> LoanTest<'_> {
+    let contract_id = env.register(CreditLineContract, ());
+    let client = CreditLineContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let user = Address::generate(env);
+    let merchant = Address::generate(env);
+    let provider = Address::generate(env);
+
+    let rep_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(env)));
+    let reputation = ReputationContractClient::new(env, &rep_id);
+
+    // The CreditLine reports repayment behavior as a score updater
+    reputation.set_updater(&admin, &contract_id, &true);
+    reputation.set_score(&admin, &user, &100);
+
+    let (token_id, token_admin) = create_token(env, &admin);
+    let registry_id = create_merchant_registry(env, &admin, &merchant);
+
+    let pool_id = env.register(LiquidityPoolContract, ());
+    let pool = LiquidityPoolContractClient::new(env, &pool_id);
+    pool.initialize(&admin, &token_id, &contract_id, &deployed_wasm_hash(env));
+    token_admin.mint(&provider, &POOL_LIQUIDITY);
+    pool.deposit(&provider, &POOL_LIQUIDITY);
 
-#[contractimpl]
-impl MockReputation {
-    pub fn get_score(_env: Env, _user: Address) -> u32 {
-        100 // Returns 100 to pass the threshold check
+    client.initialize(
+        &admin,
+        &rep_id,
+        &registry_id,
+        &pool_id,
+        &deployed_wasm_hash(env),
+    );
+    apply_change(env, &client, &admin, &ParamChange::Token(token_id.clone()));
+    token_admin.mint(&user, &1200);
+
+    // Tests use tenors of seconds rather than weeks
+    apply_change(
+        env,
+        &client,
+        &admin,
+        &ParamChange::ScheduleLimits(ScheduleLimits {
+            min_installments: 1,
+            max_installments: 12,
+            min_tenor: 0,
+            max_tenor: 365 * 24 * 60 * 60,
+        }),
+    );
+
+    env.ledger().set_timestamp(10000);
+
+    LoanTest {
+        client,
+        contract_id,
+        admin,
+        user,
+        merchant,
+        registry_id,
+        pool,
+        token: token::Client::new(env, &token_id),
+        reputation,
     }
-    pub fn slash(_env: Env, _user: Address) {
-        // Does nothing, just needs to exist for the call to succeed
+}
+
+/// Queues a parameter change, waits out the timelock and executes it
+/// The ledger time is restored afterwards so tests keep their own clock
+fn apply_change(
+    env: &Env,
+    client: &CreditLineContractClient,
+    caller: &Address,
+    change: &ParamChange,
+) {
+    let now = env.ledger().timestamp();
+    let change_id = client.queue_change(caller, change);
+    env.ledger()
+        .set_timestamp(now + client.get_timelock_delay());
+    client.execute_change(caller, &change_id);
+    env.ledger().set_timestamp(now);
+}
+
+// Tests register the contracts natively, so any hash stands in for the deployed code
+fn deployed_wasm_hash(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[1; 32])
+}
+
+fn installment(due_date: u64, amount: i128) -> RepaymentInstallment {
+    RepaymentInstallment {
+        due_date,
+        amount,
+        principal: amount,
+        interest: 0,
+        paid_amount: 0,
+        late: false,
+        missed: false,
     }
 }
 
@@ -40,6 +175,7 @@ fn test_initialize() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     assert_eq!(client.get_admin(), admin);
@@ -64,6 +200,7 @@ fn test_initialize_twice_fails() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Try to initialize again - should panic
@@ -72,13 +209,18 @@ fn test_initialize_twice_fails() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 }
 
 #[test]
 fn test_get_version() {
-    let version = CreditLineContract::get_version();
-    assert_eq!(version, soroban_sdk::symbol_short!("v1_0_0"));
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    assert_eq!(t.client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(t.client.get_wasm_hash(), Some(deployed_wasm_hash(&env)));
 }
 
 #[test]
@@ -100,6 +242,7 @@ fn test_get_loan_not_found() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Try to get a loan that doesn't exist
@@ -107,7 +250,7 @@ fn test_get_loan_not_found() {
 }
 
 #[test]
-fn test_set_admin() {
+fn test_two_step_admin_transfer() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -125,14 +268,87 @@ fn test_set_admin() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     assert_eq!(client.get_admin(), admin);
 
-    // Change admin
-    client.set_admin(&new_admin);
+    // Proposing does not hand over the role yet
+    client.propose_admin(&new_admin);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "propose_admin"),
+                    (new_admin.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    // The new admin must authorize the acceptance
+    client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("ADMINCHGD"),).into_val(&env),
+                (admin.clone(), new_admin.clone(), 0u64).into_val(&env),
+            )
+        ]
+    );
 
     assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(CreditLineError::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let typo = Address::generate(&env);
+
+    t.client.propose_admin(&typo);
+    t.client.cancel_admin_transfer();
+
+    assert_eq!(t.client.get_pending_admin(), None);
+    assert_eq!(
+        t.client.try_accept_admin(),
+        Err(Ok(CreditLineError::NoPendingAdmin))
+    );
+    assert_eq!(
+        t.client.try_cancel_admin_transfer(),
+        Err(Ok(CreditLineError::NoPendingAdmin))
+    );
+    assert_eq!(t.client.get_admin(), t.admin);
 }
 
 #[test]
@@ -154,10 +370,16 @@ fn test_set_reputation_contract() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Update reputation contract address
-    client.set_reputation_contract(&admin, &new_reputation_contract);
+    apply_change(
+        &env,
+        &client,
+        &admin,
+        &ParamChange::ReputationContract(new_reputation_contract.clone()),
+    );
 
     // Verify it was updated (we can't directly query, but no panic means success)
 }
@@ -181,10 +403,16 @@ fn test_set_merchant_registry() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Update merchant registry address
-    client.set_merchant_registry(&admin, &new_merchant_registry);
+    apply_change(
+        &env,
+        &client,
+        &admin,
+        &ParamChange::MerchantRegistry(new_merchant_registry.clone()),
+    );
 
     // Verify it was updated (we can't directly query, but no panic means success)
 }
@@ -208,10 +436,16 @@ fn test_set_liquidity_pool() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Update liquidity pool address
-    client.set_liquidity_pool(&admin, &new_liquidity_pool);
+    apply_change(
+        &env,
+        &client,
+        &admin,
+        &ParamChange::LiquidityPool(new_liquidity_pool.clone()),
+    );
 
     // Verify it was updated (we can't directly query, but no panic means success)
 }
@@ -239,12 +473,13 @@ fn test_create_loan_with_zero_total_amount() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // This should panic with InvalidAmount (error code 9)
-    client.create_loan(&user, &merchant, &0, &0, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &0, &0, &repayment_schedule);
 }
 
 #[test]
@@ -268,12 +503,13 @@ fn test_create_loan_with_negative_total_amount() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // This should panic with InvalidAmount (error code 9)
-    client.create_loan(&user, &merchant, &-1000, &-200, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &-1000, &-200, &repayment_schedule);
 }
 
 #[test]
@@ -297,12 +533,13 @@ fn test_create_loan_with_zero_guarantee_amount() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // This should panic with InvalidAmount (error code 9)
-    client.create_loan(&user, &merchant, &1000, &0, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &1000, &0, &repayment_schedule);
 }
 
 #[test]
@@ -326,12 +563,13 @@ fn test_create_loan_with_insufficient_guarantee_19_percent() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // 190 is 19% of 1000, should fail with InsufficientGuarantee (error code 2)
-    client.create_loan(&user, &merchant, &1000, &190, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &1000, &190, &repayment_schedule);
 }
 
 #[test]
@@ -355,12 +593,13 @@ fn test_create_loan_with_insufficient_guarantee_10_percent() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // 100 is 10% of 1000, should fail with InsufficientGuarantee (error code 2)
-    client.create_loan(&user, &merchant, &1000, &100, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &1000, &100, &repayment_schedule);
 }
 
 // Additional edge case tests
@@ -379,7 +618,7 @@ fn test_get_admin_before_initialization() {
 
 #[test]
 #[should_panic(expected = "Admin not set")]
-fn test_set_admin_before_initialization() {
+fn test_propose_admin_before_initialization() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -388,8 +627,8 @@ fn test_set_admin_before_initialization() {
 
     let new_admin = Address::generate(&env);
 
-    // Try to set admin before initialization - should panic
-    client.set_admin(&new_admin);
+    // Try to propose an admin before initialization - should panic
+    client.propose_admin(&new_admin);
 }
 
 #[test]
@@ -410,6 +649,7 @@ fn test_loan_counter_increments() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Note: We can't actually create loans without a reputation contract
@@ -438,12 +678,13 @@ fn test_create_loan_with_one_less_than_minimum_guarantee() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // 199 is 1 less than 20% of 1000, should fail with InsufficientGuarantee (error code 2)
-    client.create_loan(&user, &merchant, &1000, &199, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &1000, &199, &repayment_schedule);
 }
 
 #[test]
@@ -466,11 +707,22 @@ fn test_multiple_contract_address_updates() {
         &reputation_contract_1,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     // Update reputation contract multiple times
-    client.set_reputation_contract(&admin, &reputation_contract_2);
-    client.set_reputation_contract(&admin, &reputation_contract_3);
+    apply_change(
+        &env,
+        &client,
+        &admin,
+        &ParamChange::ReputationContract(reputation_contract_2.clone()),
+    );
+    apply_change(
+        &env,
+        &client,
+        &admin,
+        &ParamChange::ReputationContract(reputation_contract_3.clone()),
+    );
 
     // All updates should succeed without panic
 }
@@ -496,58 +748,82 @@ fn test_create_loan_with_positive_total_negative_guarantee() {
         &reputation_contract,
         &merchant_registry,
         &liquidity_pool,
+        &deployed_wasm_hash(&env),
     );
 
     let repayment_schedule = soroban_sdk::Vec::new(&env);
 
     // Positive total but negative guarantee should fail with InvalidAmount (error code 9)
-    client.create_loan(&user, &merchant, &1000, &-200, &repayment_schedule);
+    client.create_loan_with_schedule(&user, &merchant, &1000, &-200, &repayment_schedule);
 }
 
 #[test]
-fn test_mark_defaulted_success() {
+fn test_guarantee_must_leave_principal_to_finance() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(CreditLineContract, ());
-    let client = CreditLineContractClient::new(&env, &contract_id);
-
-    // Register our Mock Reputation contract
-    let rep_id = env.register(MockReputation, ());
-
-    let admin = Address::generate(&env);
-    let user = Address::generate(&env);
-    let merchant = Address::generate(&env);
-    let liquidity_pool = Address::generate(&env);
+    let t = setup_loan_test(&env);
+    let schedule = soroban_sdk::vec![&env, installment(10000 + YEAR / 2, 800)];
+
+    // A guarantee covering the whole purchase, or more, is rejected up front by every entrypoint
+    for guarantee in [1000i128, 1500, i128::MAX] {
+        assert_eq!(
+            t.client
+                .try_create_loan(&t.user, &t.merchant, &1000, &guarantee, &2, &(YEAR / 2)),
+            Err(Ok(CreditLineError::InvalidAmount.into()))
+        );
+        assert_eq!(
+            t.client.try_create_loan_with_schedule(
+                &t.user,
+                &t.merchant,
+                &1000,
+                &guarantee,
+                &schedule
+            ),
+            Err(Ok(CreditLineError::InvalidAmount.into()))
+        );
+        assert_eq!(
+            t.client
+                .try_quote_schedule(&t.user, &1000, &guarantee, &2, &(YEAR / 2)),
+            Err(Ok(CreditLineError::InvalidAmount.into()))
+        );
+    }
+}
 
-    client.initialize(
-        &admin,
-        &rep_id, // Pass the Mock ID
-        &Address::generate(&env),
-        &liquidity_pool,
-    );
+#[test]
+fn test_mark_defaulted_success() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    // Set a baseline time
-    let current_time = 10000;
-    env.ledger().set_timestamp(current_time);
+    let t = setup_loan_test(&env);
 
     let mut schedule = soroban_sdk::Vec::new(&env);
-    schedule.push_back(RepaymentInstallment {
-        amount: 1000,
-        due_date: current_time + 1000, // Due at 11000
-    });
+    schedule.push_back(installment(11000, 800));
+
+    // Create loan (calls ReputationContract::get_score)
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
 
-    // Create loan (calls MockReputation::get_score)
-    let loan_id = client.create_loan(&user, &merchant, &1000, &200, &schedule);
+    // Guarantee is held in escrow by the contract
+    assert_eq!(t.token.balance(&t.user), 1000);
+    assert_eq!(t.token.balance(&t.contract_id), 200);
 
     // Time Travel past the due date
     env.ledger().set_timestamp(12000);
 
-    // This calls mark_defaulted which internally calls MockReputation::slash
-    client.mark_defaulted(&loan_id);
+    // This calls mark_defaulted which internally calls ReputationContract::slash
+    t.client.mark_defaulted(&t.admin, &loan_id);
 
-    let updated_loan = client.get_loan(&loan_id);
+    let updated_loan = t.client.get_loan(&loan_id);
     assert_eq!(updated_loan.status, LoanStatus::Defaulted);
+    assert_eq!(t.reputation.get_score(&t.user), 75);
+
+    // Guarantee is forfeited to the liquidity pool and the principal written off
+    assert_eq!(t.token.balance(&t.contract_id), 0);
+    assert_eq!(t.token.balance(&t.pool.address), POOL_LIQUIDITY - 800 + 200);
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY - 800 + 200);
 }
 
 #[test]
@@ -556,32 +832,2914 @@ fn test_mark_defaulted_too_early_fails() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(CreditLineContract, ());
-    let client = CreditLineContractClient::new(&env, &contract_id);
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(20000, 800));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    // This should fail because 10000 < 20000
+    t.client.mark_defaulted(&t.admin, &loan_id);
+}
+
+// Liquidity pool tests
+
+#[test]
+fn test_create_loan_funds_merchant_from_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    // The pool pays the financed portion, the guarantee stays in escrow
+    assert_eq!(t.token.balance(&t.merchant), 800);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY - 800);
+    assert_eq!(t.pool.get_total_loaned(), 800);
+}
+
+#[test]
+fn test_create_loan_funds_merchant_payout_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    // Route the merchant's payouts to a separate account
+    let payout = Address::generate(&env);
+    MerchantRegistryContractClient::new(&env, &t.registry_id).update_merchant(
+        &t.admin,
+        &t.merchant,
+        &String::from_str(&env, "Test Merchant"),
+        &symbol_short!("retail"),
+        &payout,
+    );
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    assert_eq!(t.token.balance(&payout), 800);
+    assert_eq!(t.token.balance(&t.merchant), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")] // InsufficientLiquidity
+fn test_create_loan_with_insufficient_liquidity_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 16000));
+
+    // Needs 16_000 from a pool holding 10_000
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &20000, &4000, &schedule);
+}
+
+// Repayment tests
+
+#[test]
+fn test_repay_loan_partial() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 400));
+    schedule.push_back(installment(12000, 400));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    let status = t.client.repay_loan(&t.user, &loan_id, &300);
+    assert_eq!(status, LoanStatus::Active);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.remaining_balance, 500);
+    assert_eq!(loan.repayment_schedule.get(0).unwrap().paid_amount, 300);
+    assert_eq!(loan.repayment_schedule.get(1).unwrap().paid_amount, 0);
+
+    assert_eq!(t.token.balance(&t.user), 700);
+    assert_eq!(t.token.balance(&t.pool.address), POOL_LIQUIDITY - 800 + 300);
+    assert_eq!(t.token.balance(&t.contract_id), 200);
+}
+
+#[test]
+fn test_repay_loan_full_marks_paid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 400));
+    schedule.push_back(installment(12000, 400));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    t.client.repay_loan(&t.user, &loan_id, &500);
+    let status = t.client.repay_loan(&t.user, &loan_id, &300);
+    assert_eq!(status, LoanStatus::Paid);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Paid);
+    assert_eq!(loan.remaining_balance, 0);
+    assert_eq!(loan.repayment_schedule.get(0).unwrap().paid_amount, 400);
+    assert_eq!(loan.repayment_schedule.get(1).unwrap().paid_amount, 400);
+
+    // Guarantee is released back to the borrower
+    assert_eq!(t.token.balance(&t.user), 400);
+    assert_eq!(t.token.balance(&t.contract_id), 0);
+
+    // The pool got its principal back
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY);
+    assert_eq!(t.token.balance(&t.pool.address), POOL_LIQUIDITY);
+}
+
+#[test]
+fn test_repay_loan_applies_in_due_date_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 500));
+    schedule.push_back(installment(13000, 300));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    t.client.repay_loan(&t.user, &loan_id, &600);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.repayment_schedule.get(0).unwrap().paid_amount, 500);
+    assert_eq!(loan.repayment_schedule.get(1).unwrap().paid_amount, 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")] // NotBorrower
+fn test_repay_loan_not_borrower_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let stranger = Address::generate(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
 
-    let rep_id = env.register(MockReputation, ());
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
 
+    t.client.repay_loan(&stranger, &loan_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")] // InvalidAmount
+fn test_repay_loan_more_than_balance_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    t.client.repay_loan(&t.user, &loan_id, &801);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")] // LoanNotActive
+fn test_repay_defaulted_loan_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    env.ledger().set_timestamp(12000);
+    t.client.mark_defaulted(&t.admin, &loan_id);
+
+    t.client.repay_loan(&t.user, &loan_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "Token not configured")]
+fn test_create_loan_without_token_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CreditLineContract, ());
+    let client = CreditLineContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let registry_id = create_merchant_registry(&env, &admin, &merchant);
+
+    let rep_id = env.register(
+        ReputationContract,
+        (admin.clone(), deployed_wasm_hash(&env)),
+    );
+    let reputation = ReputationContractClient::new(&env, &rep_id);
+    reputation.set_score(&admin, &user, &100);
+
+    // Pool has liquidity, but the CreditLine itself has no token configured
+    let (token_id, token_admin) = create_token(&env, &admin);
+    let pool_id = env.register(LiquidityPoolContract, ());
+    let pool = LiquidityPoolContractClient::new(&env, &pool_id);
+    pool.initialize(&admin, &token_id, &contract_id, &deployed_wasm_hash(&env));
+    token_admin.mint(&admin, &POOL_LIQUIDITY);
+    pool.deposit(&admin, &POOL_LIQUIDITY);
 
     client.initialize(
         &admin,
         &rep_id,
+        &registry_id,
+        &pool_id,
+        &deployed_wasm_hash(&env),
+    );
+
+    env.ledger().set_timestamp(10000);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(10000 + 7 * 24 * 60 * 60, 800));
+
+    client.create_loan_with_schedule(&user, &merchant, &1000, &200, &schedule);
+}
+
+#[test]
+fn test_get_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CreditLineContract, ());
+    let client = CreditLineContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+
+    client.initialize(
+        &admin,
         &Address::generate(&env),
         &Address::generate(&env),
+        &Address::generate(&env),
+        &deployed_wasm_hash(&env),
     );
 
-    let current_time = 10000;
-    env.ledger().set_timestamp(current_time);
+    let (token_id, _) = create_token(&env, &admin);
+    apply_change(&env, &client, &admin, &ParamChange::Token(token_id.clone()));
+
+    assert_eq!(client.get_token(), token_id);
+}
+
+#[test]
+fn test_token_change_rejected_while_loans_active() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let (new_token, _) = create_token(&env, &t.admin);
+    let change_id = t
+        .client
+        .queue_change(&t.admin, &ParamChange::Token(new_token.clone()));
 
+    // A loan opened while the change waits blocks it at execution
     let mut schedule = soroban_sdk::Vec::new(&env);
-    schedule.push_back(RepaymentInstallment {
-        amount: 1000,
-        due_date: 20000,
-    });
+    schedule.push_back(installment(11000, 800));
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+    env.ledger()
+        .set_timestamp(10000 + t.client.get_timelock_delay());
+    assert_eq!(
+        t.client.try_execute_change(&t.admin, &change_id),
+        Err(Ok(CreditLineError::LoansOutstanding))
+    );
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::Token(new_token.clone())),
+        Err(Ok(CreditLineError::LoansOutstanding))
+    );
+    assert_eq!(t.client.get_token(), t.token.address);
+
+    // Once the loan is closed the token can move
+    t.client.repay_loan(
+        &t.user,
+        &loan_id,
+        &t.client.get_loan(&loan_id).remaining_balance,
+    );
+    t.client.execute_change(&t.admin, &change_id);
+    assert_eq!(t.client.get_token(), new_token);
+}
 
-    let loan_id = client.create_loan(&user, &Address::generate(&env), &1000, &200, &schedule);
+#[test]
+fn test_pool_change_rejected_while_loans_active() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    // This should fail because 10000 < 20000
-    client.mark_defaulted(&loan_id);
+    let t = setup_loan_test(&env);
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    // A fresh pool has no record of the open loan's principal
+    let new_pool = Address::generate(&env);
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::LiquidityPool(new_pool.clone())),
+        Err(Ok(CreditLineError::LoansOutstanding))
+    );
+
+    t.client.repay_loan(&t.user, &loan_id, &800);
+    assert_eq!(t.client.get_loan(&loan_id).status, LoanStatus::Paid);
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::LiquidityPool(new_pool.clone()),
+    );
+    let stored_pool = env.as_contract(&t.contract_id, || storage::get_liquidity_pool(&env));
+    assert_eq!(stored_pool, Some(new_pool));
+}
+
+// Merchant registry tests
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // MerchantNotActive
+fn test_create_loan_with_inactive_merchant_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    MerchantRegistryContractClient::new(&env, &t.registry_id)
+        .deactivate_merchant(&t.admin, &t.merchant);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // MerchantNotActive
+fn test_create_loan_with_unregistered_merchant_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    t.client
+        .create_loan_with_schedule(&t.user, &Address::generate(&env), &1000, &200, &schedule);
+}
+
+#[test]
+fn test_repay_loan_rewards_on_time_installment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 400));
+    schedule.push_back(installment(12000, 400));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    // Leave room below the maximum score for rewards
+    t.reputation.set_score(&t.admin, &t.user, &50);
+
+    // Partial payment completes nothing
+    t.client.repay_loan(&t.user, &loan_id, &300);
+    assert_eq!(t.reputation.get_score(&t.user), 50);
+
+    // Settles the first installment before its due date, rewarded as on time only
+    t.client.repay_loan(&t.user, &loan_id, &100);
+    assert_eq!(t.reputation.get_score(&t.user), 53);
+}
+
+#[test]
+fn test_repay_loan_rewards_early_installments_and_completion() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let window = crate::types::EARLY_PAYMENT_WINDOW;
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(10000 + window, 400));
+    schedule.push_back(installment(10000 + 2 * window, 400));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    t.reputation.set_score(&t.admin, &t.user, &50);
+    t.client.repay_loan(&t.user, &loan_id, &800);
+
+    // Two early installments and the completion reward
+    assert_eq!(t.reputation.get_score(&t.user), 50 + 7 + 7 + 12);
+}
+
+#[test]
+fn test_repay_loan_survives_failed_reputation_hooks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let window = crate::types::EARLY_PAYMENT_WINDOW;
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(10000 + window, 400));
+    schedule.push_back(installment(10000 + 2 * window, 400));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    t.reputation.set_score(&t.admin, &t.user, &50);
+    t.reputation.pause(&t.admin);
+
+    // A paused Reputation contract skips the score updates instead of reverting the repayment
+    t.client.repay_loan(&t.user, &loan_id, &400);
+    assert!(env.events().all().contains((
+        t.contract_id.clone(),
+        (symbol_short!("REPFAILED"), t.user.clone()).into_val(&env),
+        (symbol_short!("early"), 10000u64).into_val(&env),
+    )));
+    assert_eq!(t.client.get_loan(&loan_id).remaining_balance, 400);
+
+    // So does a credit line that lost its Updater role
+    t.reputation.unpause(&t.admin);
+    t.reputation.set_updater(&t.admin, &t.contract_id, &false);
+    t.client.repay_loan(&t.user, &loan_id, &400);
+    assert_eq!(t.client.get_loan(&loan_id).status, LoanStatus::Paid);
+    assert_eq!(t.reputation.get_score(&t.user), 50);
+}
+
+#[test]
+fn test_repay_loan_late_installment_not_rewarded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 400));
+    schedule.push_back(installment(12000, 400));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    // Past the first due date, before the second
+    t.reputation.set_score(&t.admin, &t.user, &50);
+    env.ledger().set_timestamp(11500);
+    t.client.repay_loan(&t.user, &loan_id, &800);
+
+    // Only the second installment is on time, plus the completion reward
+    assert_eq!(t.reputation.get_score(&t.user), 50 + 3 + 12);
+}
+
+fn tier(tier: CreditTier, min_score: u32, apr_bps: u32, max_credit: i128) -> TierConfig {
+    TierConfig {
+        tier,
+        min_score,
+        apr_bps,
+        max_credit,
+    }
+}
+
+#[test]
+fn test_default_credit_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let tiers = t.client.get_credit_tiers();
+    assert_eq!(tiers.len(), 5);
+    assert_eq!(tiers.get(0).unwrap().tier, CreditTier::Excellent);
+    assert_eq!(tiers.get(4).unwrap().tier, CreditTier::VeryPoor);
+    assert_eq!(tiers.get(4).unwrap().min_score, 0);
+}
+
+#[test]
+fn test_create_loan_records_tier_and_apr() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    t.reputation.set_score(&t.admin, &t.user, &80);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    let loan = t.client.get_loan(&loan_id);
+    let good = t.client.get_credit_tiers().get(1).unwrap();
+    assert_eq!(loan.tier, CreditTier::Good);
+    assert_eq!(loan.apr_bps, good.apr_bps);
+}
+
+#[test]
+fn test_set_credit_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    tiers.push_back(tier(CreditTier::Excellent, 95, 500, 5_000));
+    tiers.push_back(tier(CreditTier::Fair, 50, 1_500, 900));
+    tiers.push_back(tier(CreditTier::VeryPoor, 0, 3_000, 0));
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::CreditTiers(tiers.clone()),
+    );
+
+    assert_eq!(t.client.get_credit_tiers(), tiers);
+
+    // Score 80 now lands in the Fair tier
+    t.reputation.set_score(&t.admin, &t.user, &80);
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.tier, CreditTier::Fair);
+    assert_eq!(loan.apr_bps, 1_500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")] // CreditLimitExceeded
+fn test_create_loan_above_tier_limit_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    tiers.push_back(tier(CreditTier::Excellent, 90, 800, 799));
+    tiers.push_back(tier(CreditTier::VeryPoor, 0, 3_000, 0));
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::CreditTiers(tiers.clone()),
+    );
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+
+    // Finances 800, one above the Excellent limit
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+}
+
+#[test]
+fn test_set_credit_tiers_rejects_invalid_tables() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    // Empty table
+    let tiers = soroban_sdk::Vec::new(&env);
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::CreditTiers(tiers.clone())),
+        Err(Ok(CreditLineError::InvalidTierConfig))
+    );
+
+    // Scores not strictly decreasing
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    tiers.push_back(tier(CreditTier::Good, 60, 800, 1_000));
+    tiers.push_back(tier(CreditTier::Fair, 60, 1_200, 500));
+    tiers.push_back(tier(CreditTier::VeryPoor, 0, 3_000, 0));
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::CreditTiers(tiers.clone())),
+        Err(Ok(CreditLineError::InvalidTierConfig))
+    );
+
+    // No tier covering score 0
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    tiers.push_back(tier(CreditTier::Good, 60, 800, 1_000));
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::CreditTiers(tiers.clone())),
+        Err(Ok(CreditLineError::InvalidTierConfig))
+    );
+
+    // APR above 100%
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    tiers.push_back(tier(CreditTier::Good, 0, 10_001, 1_000));
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::CreditTiers(tiers.clone())),
+        Err(Ok(CreditLineError::InvalidTierConfig))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")] // MissingRole
+fn test_set_credit_tiers_not_risk_manager_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    tiers.push_back(tier(CreditTier::VeryPoor, 0, 3_000, 0));
+    apply_change(
+        &env,
+        &t.client,
+        &t.user,
+        &ParamChange::CreditTiers(tiers.clone()),
+    );
+}
+
+// Interest tests
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Creates a loan financing 800 over two installments due in six and twelve months
+fn create_interest_loan(env: &Env, t: &LoanTest) -> u64 {
+    let mut schedule = soroban_sdk::Vec::new(env);
+    schedule.push_back(installment(10000 + YEAR / 2, 400));
+    schedule.push_back(installment(10000 + YEAR, 400));
+
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule)
+}
+
+#[test]
+fn test_create_loan_computes_interest_per_installment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    // Score 100 is priced at the Excellent tier, 5% APR
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.apr_bps, 500);
+    assert_eq!(loan.principal, 800);
+    assert_eq!(loan.interest_amount, 30);
+    assert_eq!(loan.remaining_balance, 830);
+    assert_eq!(loan.accrued_interest, 0);
+
+    let first = loan.repayment_schedule.get(0).unwrap();
+    assert_eq!(first.principal, 400);
+    assert_eq!(first.interest, 10);
+    assert_eq!(first.amount, 410);
+
+    let second = loan.repayment_schedule.get(1).unwrap();
+    assert_eq!(second.principal, 400);
+    assert_eq!(second.interest, 20);
+    assert_eq!(second.amount, 420);
+}
+
+#[test]
+fn test_get_loan_payoff_accrues_over_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    let payoff = t.client.get_loan_payoff(&loan_id);
+    assert_eq!(
+        payoff,
+        LoanPayoff {
+            principal: 800,
+            interest: 0,
+            fees: 0,
+            total: 800,
+        }
+    );
+
+    // A quarter in, both installments have accrued 5
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+    let payoff = t.client.get_loan_payoff(&loan_id);
+    assert_eq!(payoff.interest, 10);
+    assert_eq!(payoff.total, 810);
+
+    // Interest stops accruing at each installment's due date
+    env.ledger().set_timestamp(10000 + 2 * YEAR);
+    let payoff = t.client.get_loan_payoff(&loan_id);
+    assert_eq!(payoff.interest, 30);
+    assert_eq!(payoff.total, 830);
+}
+
+#[test]
+fn test_repay_loan_splits_interest_and_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    env.ledger().set_timestamp(10000 + YEAR / 2);
+    t.client.repay_loan(&t.user, &loan_id, &410);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.interest_paid, 10);
+    assert_eq!(loan.principal_paid, 400);
+    assert_eq!(loan.accrued_interest, 20);
+    assert_eq!(loan.remaining_balance, 420);
+
+    // The pool books the principal against the loaned amount and the interest as yield
+    assert_eq!(t.pool.get_total_loaned(), 400);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY - 800 + 410);
+
+    let payoff = t.client.get_loan_payoff(&loan_id);
+    assert_eq!(payoff.principal, 400);
+    assert_eq!(payoff.interest, 10);
+    assert_eq!(payoff.total, 410);
+}
+
+#[test]
+fn test_get_loan_payoff_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    assert_eq!(
+        t.client.try_get_loan_payoff(&1),
+        Err(Ok(CreditLineError::LoanNotFound))
+    );
+
+    let loan_id = create_interest_loan(&env, &t);
+    env.ledger().set_timestamp(10000 + 2 * YEAR);
+    t.client.mark_defaulted(&t.admin, &loan_id);
+
+    assert_eq!(
+        t.client.try_get_loan_payoff(&loan_id),
+        Err(Ok(CreditLineError::LoanNotActive))
+    );
+}
+
+// Late fee tests
+
+/// Creates a loan financing 800 over installments due at 11000 and 12000
+/// with a flat fee of 10 after a 100 second grace period
+fn create_late_fee_loan(env: &Env, t: &LoanTest) -> u64 {
+    apply_change(
+        env,
+        &t.client,
+        &t.admin,
+        &ParamChange::LateFeeConfig(LateFeeConfig {
+            grace_period: 100,
+            flat_fee: 10,
+            percent_bps: 0,
+        }),
+    );
+
+    let mut schedule = soroban_sdk::Vec::new(env);
+    schedule.push_back(installment(11000, 400));
+    schedule.push_back(installment(12000, 400));
+
+    t.client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule)
+}
+
+#[test]
+fn test_assess_late_fees_after_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_late_fee_loan(&env, &t);
+
+    // Within the grace period nothing is charged
+    env.ledger().set_timestamp(11100);
+    assert_eq!(t.client.assess_late_fees(&loan_id), 0);
+
+    env.ledger().set_timestamp(11101);
+    assert_eq!(t.client.assess_late_fees(&loan_id), 10);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.fees, 10);
+    assert_eq!(loan.remaining_balance, 810);
+    assert!(loan.repayment_schedule.get(0).unwrap().late);
+    assert!(!loan.repayment_schedule.get(1).unwrap().late);
+    assert_eq!(t.reputation.get_score(&t.user), 97);
+
+    // Each installment is only charged once
+    assert_eq!(t.client.assess_late_fees(&loan_id), 0);
+    assert_eq!(t.client.get_loan(&loan_id).fees, 10);
+    assert_eq!(t.reputation.get_score(&t.user), 97);
+}
+
+#[test]
+fn test_assess_late_fees_missed_installment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_late_fee_loan(&env, &t);
+
+    // First installment is missed, second one is only late
+    env.ledger()
+        .set_timestamp(11000 + crate::types::MISSED_PAYMENT_PERIOD + 1);
+    assert_eq!(t.client.assess_late_fees(&loan_id), 20);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert!(loan.repayment_schedule.get(0).unwrap().missed);
+    assert!(!loan.repayment_schedule.get(1).unwrap().missed);
+    // Missed penalty for the first installment, late penalty for the second
+    assert_eq!(t.reputation.get_score(&t.user), 100 - 7 - 3);
+}
+
+#[test]
+fn test_assess_late_fees_percentage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_late_fee_loan(&env, &t);
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::LateFeeConfig(LateFeeConfig {
+            grace_period: 0,
+            flat_fee: 0,
+            percent_bps: 1_000,
+        }),
+    );
+
+    // 10% of the 300 still unpaid on the first installment
+    t.client.repay_loan(&t.user, &loan_id, &100);
+    env.ledger().set_timestamp(11001);
+    assert_eq!(t.client.assess_late_fees(&loan_id), 30);
+}
+
+#[test]
+fn test_repay_loan_settles_fees_first() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_late_fee_loan(&env, &t);
+
+    env.ledger().set_timestamp(11101);
+    t.client.assess_late_fees(&loan_id);
+
+    t.client.repay_loan(&t.user, &loan_id, &15);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.fees, 0);
+    assert_eq!(loan.repayment_schedule.get(0).unwrap().paid_amount, 5);
+    assert_eq!(loan.remaining_balance, 795);
+
+    // Fees reach the pool as yield, principal reduces the loaned amount
+    assert_eq!(t.pool.get_total_loaned(), 795);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY - 800 + 15);
+}
+
+#[test]
+fn test_late_fee_config_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let config = LateFeeConfig {
+        grace_period: 0,
+        flat_fee: -1,
+        percent_bps: 0,
+    };
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::LateFeeConfig(config.clone())),
+        Err(Ok(CreditLineError::InvalidLateFeeConfig))
+    );
+
+    let config = LateFeeConfig {
+        grace_period: 0,
+        flat_fee: 0,
+        percent_bps: 10_001,
+    };
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::LateFeeConfig(config.clone())),
+        Err(Ok(CreditLineError::InvalidLateFeeConfig))
+    );
+
+    assert_eq!(
+        t.client.try_assess_late_fees(&1),
+        Err(Ok(CreditLineError::LoanNotFound))
+    );
+}
+
+// Schedule validation tests
+
+#[test]
+fn test_create_loan_rejects_invalid_schedules() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let cases = [
+        // Empty schedule
+        (
+            soroban_sdk::vec![&env],
+            CreditLineError::InvalidInstallmentCount,
+        ),
+        // Due date not after the current ledger time
+        (
+            soroban_sdk::vec![&env, installment(10000, 800)],
+            CreditLineError::DueDateInPast,
+        ),
+        // Due dates out of order
+        (
+            soroban_sdk::vec![&env, installment(12000, 400), installment(11000, 400)],
+            CreditLineError::DueDatesNotIncreasing,
+        ),
+        // Duplicate due dates
+        (
+            soroban_sdk::vec![&env, installment(11000, 400), installment(11000, 400)],
+            CreditLineError::DueDatesNotIncreasing,
+        ),
+        // Last due date beyond the maximum tenor
+        (
+            soroban_sdk::vec![&env, installment(10001 + YEAR, 800)],
+            CreditLineError::InvalidTenor,
+        ),
+        // Amounts don't add up to the financed portion
+        (
+            soroban_sdk::vec![&env, installment(11000, 400), installment(12000, 500)],
+            CreditLineError::ScheduleAmountMismatch,
+        ),
+        // Non-positive installment
+        (
+            soroban_sdk::vec![&env, installment(11000, 800), installment(12000, 0)],
+            CreditLineError::InvalidAmount,
+        ),
+    ];
+
+    for (schedule, error) in cases {
+        assert_eq!(
+            t.client
+                .try_create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule),
+            Err(Ok(error.into()))
+        );
+    }
+}
+
+#[test]
+fn test_custom_schedule_amounts_are_principal_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    // Installments that already include the 30 of interest are rejected
+    let with_interest = soroban_sdk::vec![
+        &env,
+        installment(10000 + YEAR / 2, 415),
+        installment(10000 + YEAR, 415)
+    ];
+    assert_eq!(
+        t.client
+            .try_create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &with_interest),
+        Err(Ok(CreditLineError::ScheduleAmountMismatch.into()))
+    );
+
+    // Principal-only installments are accepted and priced on top
+    let loan_id = create_interest_loan(&env, &t);
+    let loan = t.client.get_loan(&loan_id);
+    let scheduled: i128 = loan.repayment_schedule.iter().map(|i| i.amount).sum();
+    assert_eq!(loan.interest_amount, 30);
+    assert_eq!(scheduled, loan.principal + loan.interest_amount);
+    assert_eq!(scheduled, loan.remaining_balance);
+}
+
+#[test]
+fn test_create_loan_enforces_schedule_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::ScheduleLimits(ScheduleLimits {
+            min_installments: 2,
+            max_installments: 3,
+            min_tenor: 5000,
+            max_tenor: 10000,
+        }),
+    );
+
+    let too_few = soroban_sdk::vec![&env, installment(16000, 800)];
+    assert_eq!(
+        t.client
+            .try_create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &too_few),
+        Err(Ok(CreditLineError::InvalidInstallmentCount.into()))
+    );
+
+    let too_short = soroban_sdk::vec![&env, installment(11000, 400), installment(12000, 400)];
+    assert_eq!(
+        t.client
+            .try_create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &too_short),
+        Err(Ok(CreditLineError::InvalidTenor.into()))
+    );
+
+    let valid = soroban_sdk::vec![&env, installment(12000, 400), installment(15000, 400)];
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &valid);
+    assert_eq!(t.client.get_loan(&loan_id).repayment_schedule.len(), 2);
+}
+
+#[test]
+fn test_default_and_invalid_schedule_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(CreditLineContract, ());
+    let client = CreditLineContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &deployed_wasm_hash(&env),
+    );
+
+    let limits = client.get_schedule_limits();
+    assert_eq!(limits.min_installments, 1);
+    assert_eq!(limits.max_installments, 12);
+    assert_eq!(limits.min_tenor, 7 * 24 * 60 * 60);
+    assert_eq!(limits.max_tenor, YEAR);
+
+    let invalid = [
+        ScheduleLimits {
+            min_installments: 0,
+            ..limits.clone()
+        },
+        ScheduleLimits {
+            min_installments: 13,
+            ..limits.clone()
+        },
+        ScheduleLimits {
+            min_tenor: YEAR + 1,
+            ..limits.clone()
+        },
+    ];
+    for limits in invalid {
+        assert_eq!(
+            client.try_queue_change(&admin, &ParamChange::ScheduleLimits(limits.clone())),
+            Err(Ok(CreditLineError::InvalidScheduleLimits))
+        );
+    }
+}
+
+// Schedule generator tests
+
+#[test]
+fn test_quote_schedule_equal_installments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    // 800 financed at 5% APR over four quarterly installments
+    let schedule = t
+        .client
+        .quote_schedule(&t.user, &1000, &200, &4, &(YEAR / 4));
+
+    assert_eq!(schedule.len(), 4);
+    for (i, installment) in schedule.iter().enumerate() {
+        assert_eq!(installment.due_date, 10000 + (i as u64 + 1) * YEAR / 4);
+        assert_eq!(installment.principal, 200);
+        assert_eq!(installment.interest, 6);
+        assert_eq!(installment.amount, 206);
+        assert_eq!(installment.paid_amount, 0);
+    }
+}
+
+#[test]
+fn test_quote_schedule_rounding_goes_to_last_installment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let schedule = t.client.quote_schedule(&t.user, &1000, &200, &3, &1000);
+
+    assert_eq!(schedule.get(0).unwrap().principal, 266);
+    assert_eq!(schedule.get(1).unwrap().principal, 266);
+    assert_eq!(schedule.get(2).unwrap().principal, 268);
+}
+
+#[test]
+fn test_create_loan_uses_quoted_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let quote = t
+        .client
+        .quote_schedule(&t.user, &1000, &200, &4, &(YEAR / 4));
+    let loan_id = t
+        .client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &4, &(YEAR / 4));
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.repayment_schedule, quote);
+    assert_eq!(loan.principal, 800);
+    assert_eq!(loan.interest_amount, 24);
+    assert_eq!(loan.remaining_balance, 824);
+    assert_eq!(t.token.balance(&t.merchant), 800);
+}
+
+#[test]
+fn test_quote_schedule_validates_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    assert_eq!(
+        t.client.try_quote_schedule(&t.user, &1000, &200, &0, &1000),
+        Err(Ok(CreditLineError::InvalidInstallmentCount.into()))
+    );
+    // Rejected before any installment is generated
+    assert_eq!(
+        t.client
+            .try_quote_schedule(&t.user, &1000, &200, &u32::MAX, &1000),
+        Err(Ok(CreditLineError::InvalidInstallmentCount.into()))
+    );
+    assert_eq!(
+        t.client
+            .try_create_loan(&t.user, &t.merchant, &1000, &200, &u32::MAX, &1000),
+        Err(Ok(CreditLineError::InvalidInstallmentCount.into()))
+    );
+    assert_eq!(
+        t.client.try_quote_schedule(&t.user, &1000, &200, &2, &0),
+        Err(Ok(CreditLineError::DueDateInPast.into()))
+    );
+    assert_eq!(
+        t.client.try_quote_schedule(&t.user, &1000, &100, &2, &1000),
+        Err(Ok(CreditLineError::InsufficientGuarantee.into()))
+    );
+}
+
+// Storage tests
+
+#[test]
+fn test_loans_stored_under_own_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = t
+        .client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &2, &1000);
+
+    env.as_contract(&t.contract_id, || {
+        let key = DataKey::Loan(loan_id);
+        assert!(env.storage().persistent().has(&key));
+        assert_eq!(
+            env.storage().persistent().get_ttl(&key),
+            crate::types::LOAN_TTL_EXTEND_TO
+        );
+        assert!(!env.storage().instance().has(&storage::LOANS_MAP));
+    });
+}
+
+// Loan layout written by the first release, copied here so the tests don't depend on
+// the contract's own legacy types
+mod baseline {
+    use soroban_sdk::{contracttype, Address, Vec};
+
+    use crate::LoanStatus;
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RepaymentInstallment {
+        pub due_date: u64,
+        pub amount: i128,
+    }
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct Loan {
+        pub loan_id: u64,
+        pub borrower: Address,
+        pub merchant: Address,
+        pub total_amount: i128,
+        pub guarantee_amount: i128,
+        pub remaining_balance: i128,
+        pub repayment_schedule: Vec<RepaymentInstallment>,
+        pub status: LoanStatus,
+        pub created_at: u64,
+    }
+}
+
+fn baseline_loan(
+    env: &Env,
+    t: &LoanTest,
+    loan_id: u64,
+    total_amount: i128,
+    status: LoanStatus,
+) -> baseline::Loan {
+    baseline::Loan {
+        loan_id,
+        borrower: t.user.clone(),
+        merchant: t.merchant.clone(),
+        total_amount,
+        guarantee_amount: total_amount / 5,
+        remaining_balance: if status == LoanStatus::Active {
+            total_amount
+        } else {
+            0
+        },
+        repayment_schedule: vec![
+            env,
+            baseline::RepaymentInstallment {
+                due_date: 11000,
+                amount: total_amount / 2,
+            },
+            baseline::RepaymentInstallment {
+                due_date: 12000,
+                amount: total_amount / 2,
+            },
+        ],
+        status,
+        created_at: 9000,
+    }
+}
+
+// Write loans to the legacy instance map the way the first release did
+fn seed_baseline_loans(env: &Env, t: &LoanTest, loans: &[baseline::Loan]) {
+    env.as_contract(&t.contract_id, || {
+        let mut map = Map::new(env);
+        for loan in loans {
+            map.set(loan.loan_id, loan.clone());
+        }
+        env.storage().instance().set(&storage::LOANS_MAP, &map);
+        env.storage()
+            .instance()
+            .set(&storage::LOAN_COUNTER, &(loans.len() as u64));
+    });
+}
+
+#[test]
+fn test_migrate_loans_from_legacy_map() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    seed_baseline_loans(
+        &env,
+        &t,
+        &[
+            baseline_loan(&env, &t, 1, 1000, LoanStatus::Active),
+            baseline_loan(&env, &t, 2, 500, LoanStatus::Paid),
+        ],
+    );
+
+    // Legacy loans stay readable until migrated, converted to the current layout
+    let active = t.client.get_loan(&1);
+    assert_eq!(active.status, LoanStatus::Active);
+    assert_eq!(active.principal, 800);
+    assert_eq!(active.remaining_balance, 800);
+    assert_eq!(active.interest_amount, 0);
+    assert_eq!(active.apr_bps, 0);
+    assert_eq!(active.repayment_schedule.len(), 2);
+    assert_eq!(active.repayment_schedule.get(0).unwrap().principal, 500);
+    assert_eq!(active.repayment_schedule.get(0).unwrap().interest, 0);
+    let paid = t.client.get_loan(&2);
+    assert_eq!(paid.status, LoanStatus::Paid);
+    assert_eq!(paid.remaining_balance, 0);
+    assert_eq!(paid.principal_paid, 400);
+
+    assert_eq!(t.client.migrate_loans(&t.admin, &1), 1);
+    assert_eq!(t.client.migrate_loans(&t.admin, &10), 0);
+
+    env.as_contract(&t.contract_id, || {
+        assert!(env.storage().persistent().has(&DataKey::Loan(1)));
+        assert!(env.storage().persistent().has(&DataKey::Loan(2)));
+        assert!(!env.storage().instance().has(&storage::LOANS_MAP));
+        assert_eq!(storage::get_active_loan_count(&env), 1);
+    });
+    assert_eq!(t.client.get_loan(&1), active);
+    assert_eq!(t.client.get_loan(&2), paid);
+
+    // Both loans are indexed, only the active one counts towards exposure
+    assert_eq!(
+        t.client.get_loans_by_borrower(&t.user, &0, &10),
+        vec![&env, active.clone(), paid.clone()]
+    );
+    assert_eq!(
+        t.client.get_loans_by_merchant(&t.merchant, &0, &10),
+        vec![&env, active, paid]
+    );
+    let exposure = t.client.get_borrower_exposure(&t.user);
+    assert_eq!(exposure.active_loans, 1);
+    assert_eq!(exposure.outstanding_principal, 800);
+
+    // Nothing left to migrate
+    assert_eq!(t.client.migrate_loans(&t.admin, &10), 0);
+}
+
+#[test]
+fn test_migrate_loans_keeps_newer_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = t
+        .client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &2, &1000);
+    t.client.repay_loan(&t.user, &loan_id, &100);
+
+    // A stale first release copy of the same loan id
+    seed_baseline_loans(
+        &env,
+        &t,
+        &[baseline_loan(&env, &t, loan_id, 1000, LoanStatus::Active)],
+    );
+
+    t.client.migrate_loans(&t.admin, &10);
+
+    assert_eq!(t.client.get_loan(&loan_id).remaining_balance, 700);
+    assert_eq!(t.client.get_loans_by_borrower(&t.user, &0, &10).len(), 1);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")] // NotAdmin
+fn test_migrate_loans_not_admin_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    t.client.migrate_loans(&t.user, &10);
+}
+
+// Loan index tests
+
+#[test]
+fn test_get_loans_by_borrower_and_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &t.token.address).mint(&other, &200);
+    t.reputation.set_score(&t.admin, &other, &100);
+
+    for _ in 0..3 {
+        t.client
+            .create_loan(&t.user, &t.merchant, &1000, &200, &2, &1000);
+    }
+    let other_loan = t
+        .client
+        .create_loan(&other, &t.merchant, &1000, &200, &2, &1000);
+
+    let loans = t.client.get_loans_by_borrower(&t.user, &0, &10);
+    assert_eq!(loans.len(), 3);
+    for (i, loan) in loans.iter().enumerate() {
+        assert_eq!(loan.loan_id, i as u64 + 1);
+        assert_eq!(loan.borrower, t.user);
+    }
+
+    // Pages are slices of the index, oldest first
+    let page = t.client.get_loans_by_borrower(&t.user, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().loan_id, 2);
+    assert_eq!(t.client.get_loans_by_borrower(&t.user, &3, &10).len(), 0);
+    assert_eq!(
+        t.client.get_loans_by_borrower(&t.user, &1, &u32::MAX).len(),
+        2
+    );
+
+    let other_loans = t.client.get_loans_by_borrower(&other, &0, &10);
+    assert_eq!(other_loans.len(), 1);
+    assert_eq!(other_loans.get(0).unwrap().loan_id, other_loan);
+
+    let merchant_loans = t.client.get_loans_by_merchant(&t.merchant, &0, &10);
+    assert_eq!(merchant_loans.len(), 4);
+    assert_eq!(merchant_loans.get(3).unwrap().borrower, other);
+
+    // Each index slot is its own entry next to the index length
+    env.as_contract(&t.contract_id, || {
+        let slot: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MerchantLoan(t.merchant.clone(), 3))
+            .unwrap();
+        assert_eq!(slot, other_loan);
+        let len: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MerchantLoanCount(t.merchant.clone()))
+            .unwrap();
+        assert_eq!(len, 4);
+    });
+
+    assert_eq!(
+        t.client
+            .get_loans_by_merchant(&Address::generate(&env), &0, &10)
+            .len(),
+        0
+    );
+}
+
+// Ledger entries a Soroban transaction may read
+const MAX_READ_ENTRIES: u32 = 40;
+
+#[test]
+fn test_get_loans_page_size_is_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    token::StellarAssetClient::new(&env, &t.token.address).mint(&t.user, &20_000);
+
+    let count = crate::types::MAX_PAGE_SIZE + 2;
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::ExposureLimits(ExposureLimits {
+            max_active_loans: count,
+            max_outstanding: 10_000,
+        }),
+    );
+    for _ in 0..count {
+        t.client
+            .create_loan(&t.user, &t.merchant, &100, &20, &1, &1000);
+    }
+
+    let page = t.client.get_loans_by_borrower(&t.user, &0, &count);
+    assert_eq!(page.len(), crate::types::MAX_PAGE_SIZE);
+
+    // A full page stays within the network's read entry limit, plus the contract code entry
+    // that isn't metered for natively registered contracts
+    let reads = env.cost_estimate().resources().read_entries + 1;
+    assert!(reads <= MAX_READ_ENTRIES, "{reads} ledger entries read");
+}
+
+#[test]
+fn test_get_active_loan_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    token::StellarAssetClient::new(&env, &t.token.address).mint(&t.user, &1000);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 0);
+
+    let paid = t
+        .client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &1, &1000);
+    let defaulted = t
+        .client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &1, &1000);
+    t.client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &1, &1000);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 3);
+
+    t.client.repay_loan(&t.user, &paid, &800);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 2);
+
+    env.ledger().set_timestamp(12000);
+    t.client.mark_defaulted(&t.admin, &defaulted);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 1);
+}
+
+// Exposure limit tests
+
+#[test]
+fn test_get_borrower_exposure() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let first = t
+        .client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &2, &1000);
+    t.client
+        .create_loan(&t.user, &t.merchant, &500, &100, &2, &1000);
+
+    assert_eq!(
+        t.client.get_borrower_exposure(&t.user),
+        BorrowerExposure {
+            active_loans: 2,
+            outstanding_principal: 1200,
+        }
+    );
+
+    // Repaid principal is released, the loan stays open until paid
+    t.client.repay_loan(&t.user, &first, &300);
+    let exposure = t.client.get_borrower_exposure(&t.user);
+    assert_eq!(exposure.active_loans, 2);
+    assert_eq!(exposure.outstanding_principal, 900);
+
+    t.client.repay_loan(&t.user, &first, &500);
+    assert_eq!(
+        t.client.get_borrower_exposure(&t.user),
+        BorrowerExposure {
+            active_loans: 1,
+            outstanding_principal: 400,
+        }
+    );
+}
+
+#[test]
+fn test_create_loan_enforces_exposure_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::ExposureLimits(ExposureLimits {
+            max_active_loans: 2,
+            max_outstanding: 1000,
+        }),
+    );
+
+    t.client
+        .create_loan(&t.user, &t.merchant, &1000, &200, &2, &1000);
+
+    // 800 + 400 is above the outstanding limit
+    assert_eq!(
+        t.client
+            .try_create_loan(&t.user, &t.merchant, &500, &100, &2, &1000),
+        Err(Ok(CreditLineError::ExposureLimitExceeded.into()))
+    );
+
+    t.client
+        .create_loan(&t.user, &t.merchant, &250, &50, &2, &1000);
+
+    // Third loan is above the active loan limit
+    assert_eq!(
+        t.client
+            .try_create_loan(&t.user, &t.merchant, &100, &20, &2, &1000),
+        Err(Ok(CreditLineError::TooManyActiveLoans.into()))
+    );
+}
+
+#[test]
+fn test_exposure_limits_per_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let excellent = ExposureLimits {
+        max_active_loans: 1,
+        max_outstanding: 10_000,
+    };
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::TierExposureLimits(CreditTier::Excellent, excellent.clone()),
+    );
+
+    assert_eq!(
+        t.client.get_exposure_limits(&Some(CreditTier::Excellent)),
+        excellent
+    );
+    assert_eq!(
+        t.client.get_exposure_limits(&Some(CreditTier::Good)),
+        t.client.get_exposure_limits(&None)
+    );
+
+    // Score 100 is Excellent and limited to one loan
+    t.client
+        .create_loan(&t.user, &t.merchant, &100, &20, &2, &1000);
+    assert_eq!(
+        t.client
+            .try_create_loan(&t.user, &t.merchant, &100, &20, &2, &1000),
+        Err(Ok(CreditLineError::TooManyActiveLoans.into()))
+    );
+
+    // Score 80 is Good and uses the default limits
+    t.reputation.set_score(&t.admin, &t.user, &80);
+    t.client
+        .create_loan(&t.user, &t.merchant, &100, &20, &2, &1000);
+
+    // Clearing the override restores the default limits for Excellent
+    apply_change(
+        &env,
+        &t.client,
+        &t.admin,
+        &ParamChange::ClearExposureLimits(CreditTier::Excellent),
+    );
+    t.reputation.set_score(&t.admin, &t.user, &100);
+    t.client
+        .create_loan(&t.user, &t.merchant, &100, &20, &2, &1000);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 3);
+}
+
+#[test]
+fn test_set_exposure_limits_rejects_invalid_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    for limits in [
+        ExposureLimits {
+            max_active_loans: 0,
+            max_outstanding: 1000,
+        },
+        ExposureLimits {
+            max_active_loans: 1,
+            max_outstanding: 0,
+        },
+    ] {
+        assert_eq!(
+            t.client
+                .try_queue_change(&t.admin, &ParamChange::ExposureLimits(limits.clone())),
+            Err(Ok(CreditLineError::InvalidExposureLimits))
+        );
+    }
+}
+
+// Early payoff tests
+
+#[test]
+fn test_payoff_loan_waives_unaccrued_interest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    t.reputation.set_score(&t.admin, &t.user, &50);
+
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+    let payoff = t.client.payoff_loan(&t.user, &loan_id);
+
+    let expected = LoanPayoff {
+        principal: 800,
+        interest: 10,
+        fees: 0,
+        total: 810,
+    };
+    assert_eq!(payoff, expected);
+
+    // The 20 of unaccrued interest is rebated in full by default, then the early payoff is rewarded
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 2..),
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("LOANPOFF"), t.user.clone(), loan_id).into_val(&env),
+                (800i128, 10i128, 0i128, 20i128, 10000 + YEAR / 4).into_val(&env),
+            ),
+            (
+                t.reputation.address.clone(),
+                (symbol_short!("SCORECHGD"), t.user.clone()).into_val(&env),
+                (50u32, 57u32, symbol_short!("early")).into_val(&env),
+            )
+        ]
+    );
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Paid);
+    assert_eq!(loan.remaining_balance, 0);
+    assert_eq!(loan.principal_paid, 800);
+    assert_eq!(loan.interest_paid, 10);
+    for installment in loan.repayment_schedule.iter() {
+        assert_eq!(installment.paid_amount, installment.amount);
+    }
+
+    // Guarantee released, pool repaid with the accrued interest
+    assert_eq!(t.token.balance(&t.user), 1200 - 810);
+    assert_eq!(t.token.balance(&t.contract_id), 0);
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY + 10);
+
+    // Paid off early, without the completion reward
+    assert_eq!(t.reputation.get_score(&t.user), 57);
+    assert_eq!(
+        t.client.get_borrower_exposure(&t.user),
+        BorrowerExposure::default()
+    );
+}
+
+#[test]
+fn test_payoff_loan_partial_rebate() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    apply_change(&env, &t.client, &t.admin, &ParamChange::PayoffRebate(5_000));
+
+    // Half of the 20 unaccrued interest is still charged
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+    assert_eq!(t.client.get_loan_payoff(&loan_id).total, 820);
+    assert_eq!(t.client.payoff_loan(&t.user, &loan_id).interest, 20);
+
+    // No rebate charges the full scheduled interest
+    let loan_id = create_interest_loan(&env, &t);
+    apply_change(&env, &t.client, &t.admin, &ParamChange::PayoffRebate(0));
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(
+        t.client.get_loan_payoff(&loan_id).interest,
+        loan.interest_amount
+    );
+}
+
+#[test]
+fn test_payoff_loan_after_partial_repayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    // First installment paid on its due date, then the rest is settled early
+    env.ledger().set_timestamp(10000 + YEAR / 2);
+    t.client.repay_loan(&t.user, &loan_id, &410);
+
+    let payoff = t.client.payoff_loan(&t.user, &loan_id);
+    assert_eq!(payoff.principal, 400);
+    assert_eq!(payoff.interest, 10);
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY + 20);
+}
+
+#[test]
+fn test_payoff_loan_right_after_creation_earns_no_bonus() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    t.reputation.set_score(&t.admin, &t.user, &50);
+    token::StellarAssetClient::new(&env, &t.token.address).mint(&t.user, &2000);
+
+    // Settled in the ledger it was created in, nothing is charged and nothing is rewarded
+    let loan_id = create_interest_loan(&env, &t);
+    let payoff = t.client.payoff_loan(&t.user, &loan_id);
+    assert_eq!(payoff.interest, 0);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (symbol_short!("LOANPOFF"), t.user.clone(), loan_id).into_val(&env)
+    );
+    assert_eq!(t.reputation.get_score(&t.user), 50);
+
+    // Still too young one second before the window ends
+    let loan_id = create_interest_loan(&env, &t);
+    env.ledger()
+        .set_timestamp(10000 + crate::types::EARLY_PAYMENT_WINDOW - 1);
+    t.client.payoff_loan(&t.user, &loan_id);
+    assert_eq!(t.reputation.get_score(&t.user), 50);
+
+    // Once the loan is a full window old the payoff is rewarded
+    let loan_id = create_interest_loan(&env, &t);
+    env.ledger()
+        .set_timestamp(10000 + 2 * crate::types::EARLY_PAYMENT_WINDOW);
+    t.client.payoff_loan(&t.user, &loan_id);
+    assert_eq!(t.reputation.get_score(&t.user), 57);
+}
+
+#[test]
+fn test_payoff_loan_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    assert_eq!(
+        t.client.try_payoff_loan(&Address::generate(&env), &loan_id),
+        Err(Ok(CreditLineError::NotBorrower))
+    );
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::PayoffRebate(10_001)),
+        Err(Ok(CreditLineError::InvalidRebate))
+    );
+
+    t.client.payoff_loan(&t.user, &loan_id);
+    assert_eq!(
+        t.client.try_payoff_loan(&t.user, &loan_id),
+        Err(Ok(CreditLineError::LoanNotActive))
+    );
+}
+
+// Refund tests
+
+#[test]
+fn test_refund_loan_in_full_cancels_loan() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    let refund = t.client.refund_loan(&t.merchant, &loan_id, &800);
+    assert_eq!(
+        refund,
+        LoanRefund {
+            to_pool: 800,
+            to_borrower: 0,
+            guarantee_returned: 200,
+            interest_waived: 30,
+        }
+    );
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Cancelled);
+    assert_eq!(loan.remaining_balance, 0);
+    assert_eq!(loan.principal, 0);
+    assert_eq!(loan.interest_amount, 0);
+    assert_eq!(loan.total_amount, 0);
+
+    // Every party is back where it started
+    assert_eq!(t.token.balance(&t.user), 1200);
+    assert_eq!(t.token.balance(&t.merchant), 0);
+    assert_eq!(t.token.balance(&t.contract_id), 0);
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY);
+    assert_eq!(
+        t.client.get_borrower_exposure(&t.user),
+        BorrowerExposure::default()
+    );
+
+    // A refund is not the borrower's doing
+    assert_eq!(t.reputation.get_score(&t.user), 100);
+}
+
+#[test]
+fn test_refund_loan_returns_repayments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    env.ledger().set_timestamp(10000 + YEAR / 2);
+    t.client.repay_loan(&t.user, &loan_id, &410);
+
+    let refund = t.client.refund_loan(&t.merchant, &loan_id, &800);
+    assert_eq!(
+        refund,
+        LoanRefund {
+            to_pool: 400,
+            to_borrower: 400,
+            guarantee_returned: 200,
+            interest_waived: 20,
+        }
+    );
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("LOANRFND"), t.merchant.clone(), loan_id).into_val(&env),
+                (400i128, 400i128, 200i128, 0i128, 10000 + YEAR / 2).into_val(&env),
+            )
+        ]
+    );
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Refunded);
+    assert_eq!(loan.principal_paid, 0);
+    assert_eq!(loan.interest_paid, 10);
+
+    // Interest already paid stays with the pool
+    assert_eq!(t.token.balance(&t.user), 1200 - 10);
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY + 10);
+    assert_eq!(t.client.get_active_loan_count(&t.user), 0);
+}
+
+#[test]
+fn test_partial_refund_shrinks_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    let refund = t.client.refund_loan(&t.merchant, &loan_id, &400);
+    assert_eq!(refund.to_pool, 400);
+    assert_eq!(refund.guarantee_returned, 100);
+    assert_eq!(refund.interest_waived, 15);
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Active);
+    assert_eq!(loan.total_amount, 500);
+    assert_eq!(loan.guarantee_amount, 100);
+    assert_eq!(loan.principal, 400);
+    assert_eq!(loan.remaining_balance, 415);
+
+    let first = loan.repayment_schedule.get(0).unwrap();
+    assert_eq!(
+        (first.principal, first.interest, first.amount),
+        (200, 5, 205)
+    );
+    let second = loan.repayment_schedule.get(1).unwrap();
+    assert_eq!(
+        (second.principal, second.interest, second.amount),
+        (200, 10, 210)
+    );
+
+    let exposure = t.client.get_borrower_exposure(&t.user);
+    assert_eq!(exposure.active_loans, 1);
+    assert_eq!(exposure.outstanding_principal, 400);
+
+    // The smaller loan is repaid as usual and releases what is left of the guarantee
+    env.ledger().set_timestamp(10000 + YEAR);
+    assert_eq!(
+        t.client.repay_loan(&t.user, &loan_id, &415),
+        LoanStatus::Paid
+    );
+    // The borrower paid for the half they kept, with interest
+    assert_eq!(t.token.balance(&t.user), 1200 - 415);
+    assert_eq!(t.token.balance(&t.merchant), 400);
+    assert_eq!(t.pool.get_total_loaned(), 0);
+}
+
+#[test]
+fn test_refund_loan_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    assert_eq!(
+        t.client
+            .try_refund_loan(&Address::generate(&env), &loan_id, &100),
+        Err(Ok(CreditLineError::NotMerchant))
+    );
+    assert_eq!(
+        t.client.try_refund_loan(&t.merchant, &loan_id, &0),
+        Err(Ok(CreditLineError::InvalidAmount))
+    );
+    assert_eq!(
+        t.client.try_refund_loan(&t.merchant, &loan_id, &801),
+        Err(Ok(CreditLineError::InvalidAmount))
+    );
+
+    t.client.refund_loan(&t.merchant, &loan_id, &800);
+    assert_eq!(
+        t.client.try_refund_loan(&t.merchant, &loan_id, &100),
+        Err(Ok(CreditLineError::LoanNotActive))
+    );
+    assert_eq!(
+        t.client.try_repay_loan(&t.user, &loan_id, &100),
+        Err(Ok(CreditLineError::LoanNotActive))
+    );
+}
+
+// Restructure tests
+
+/// Two installments of `amount` each, due twelve and fifteen months after the loan opened
+/// The ledger must be at least three months in for the tenor to fit the one year limit
+fn restructured_schedule(env: &Env, amount: i128) -> soroban_sdk::Vec<RepaymentInstallment> {
+    let mut schedule = soroban_sdk::Vec::new(env);
+    schedule.push_back(installment(10000 + YEAR, amount));
+    schedule.push_back(installment(10000 + YEAR + YEAR / 4, amount));
+    schedule
+}
+
+#[test]
+fn test_refund_loan_rejects_restructured_loan() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    t.client.repay_loan(&t.user, &loan_id, &410);
+
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+    let proposal_id =
+        t.client
+            .propose_restructure(&t.user, &loan_id, &restructured_schedule(&env, 420));
+    assert!(t
+        .client
+        .approve_restructure(&t.admin, &loan_id, &proposal_id));
+
+    // The new schedule starts from zero paid, so the repaid principal cannot be refunded from it
+    assert_eq!(
+        t.client.try_refund_loan(&t.merchant, &loan_id, &800),
+        Err(Ok(CreditLineError::LoanRestructured))
+    );
+    assert_eq!(t.client.get_loan(&loan_id).status, LoanStatus::Active);
+}
+
+#[test]
+fn test_restructure_loan_with_both_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    let original = t.client.get_loan(&loan_id).repayment_schedule;
+
+    // 830 is owed, the longer schedule adds 10 of interest
+    let now = 10000 + YEAR / 4;
+    env.ledger().set_timestamp(now);
+    let proposal_id =
+        t.client
+            .propose_restructure(&t.user, &loan_id, &restructured_schedule(&env, 420));
+
+    let proposal = t.client.get_restructure_proposal(&loan_id).unwrap();
+    assert!(proposal.borrower_approved);
+    assert!(!proposal.admin_approved);
+
+    assert!(t
+        .client
+        .approve_restructure(&t.admin, &loan_id, &proposal_id));
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 2..),
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("LOANRSTR"), t.user.clone(), loan_id).into_val(&env),
+                (830i128, 840i128, 2u32, now).into_val(&env),
+            ),
+            (
+                t.reputation.address.clone(),
+                (symbol_short!("SCORECHGD"), t.user.clone()).into_val(&env),
+                (100u32, 90u32, symbol_short!("restruct")).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(t.client.get_restructure_proposal(&loan_id), None);
+    assert_eq!(
+        t.client.get_schedule_history(&loan_id),
+        vec![
+            &env,
+            ScheduleRevision {
+                replaced_at: now,
+                schedule: original,
+            }
+        ]
+    );
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.status, LoanStatus::Active);
+    assert_eq!(loan.remaining_balance, 840);
+    assert_eq!(loan.interest_amount, 40);
+    assert_eq!(loan.restructured_at, Some(now));
+    for installment in loan.repayment_schedule.iter() {
+        assert_eq!(installment.principal, 400);
+        assert_eq!(installment.interest, 20);
+        assert_eq!(installment.paid_amount, 0);
+    }
+
+    // Penalized, but less than a default
+    assert_eq!(t.reputation.get_score(&t.user), 90);
+
+    // Interest accrued before the restructure is not rebated on payoff
+    assert_eq!(t.client.get_loan_payoff(&loan_id).interest, 10);
+
+    env.ledger().set_timestamp(10000 + YEAR + YEAR / 4);
+    assert_eq!(t.client.get_loan_payoff(&loan_id).interest, 40);
+    assert_eq!(
+        t.client.repay_loan(&t.user, &loan_id, &840),
+        LoanStatus::Paid
+    );
+    assert_eq!(t.pool.get_total_loaned(), 0);
+    assert_eq!(t.pool.get_available_liquidity(), POOL_LIQUIDITY + 40);
+}
+
+#[test]
+fn test_restructure_after_partial_repayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    env.ledger().set_timestamp(10000 + YEAR / 2);
+    t.client.repay_loan(&t.user, &loan_id, &410);
+
+    // The admin proposes stretching the remaining 420 over two smaller installments
+    let proposal_id =
+        t.client
+            .propose_restructure(&t.admin, &loan_id, &restructured_schedule(&env, 210));
+    assert!(t
+        .client
+        .approve_restructure(&t.user, &loan_id, &proposal_id));
+
+    let loan = t.client.get_loan(&loan_id);
+    assert_eq!(loan.remaining_balance, 420);
+    assert_eq!(loan.interest_amount, 30);
+    let first = loan.repayment_schedule.get(0).unwrap();
+    assert_eq!((first.principal, first.interest), (200, 10));
+}
+
+#[test]
+fn test_restructure_approvals_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+
+    assert_eq!(
+        t.client.try_approve_restructure(&t.admin, &loan_id, &1),
+        Err(Ok(CreditLineError::NoRestructureProposal))
+    );
+
+    // Approving twice as the same party does not apply the schedule
+    let proposal_id =
+        t.client
+            .propose_restructure(&t.admin, &loan_id, &restructured_schedule(&env, 420));
+    assert!(!t
+        .client
+        .approve_restructure(&t.admin, &loan_id, &proposal_id));
+
+    t.client.cancel_restructure(&t.user, &loan_id);
+    assert_eq!(t.client.get_restructure_proposal(&loan_id), None);
+    assert_eq!(
+        t.client
+            .try_approve_restructure(&t.user, &loan_id, &proposal_id),
+        Err(Ok(CreditLineError::NoRestructureProposal))
+    );
+    assert_eq!(t.client.get_schedule_history(&loan_id).len(), 0);
+    assert_eq!(t.reputation.get_score(&t.user), 100);
+}
+
+#[test]
+fn test_restructure_approval_names_reviewed_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+
+    let reviewed =
+        t.client
+            .propose_restructure(&t.user, &loan_id, &restructured_schedule(&env, 420));
+
+    // The borrower swaps in another schedule before the risk manager approves
+    let mut swapped = soroban_sdk::Vec::new(&env);
+    for due_date in [10000 + YEAR / 2, 10000 + YEAR, 10000 + YEAR + YEAR / 4] {
+        swapped.push_back(installment(due_date, 280));
+    }
+    let replacement = t.client.propose_restructure(&t.user, &loan_id, &swapped);
+    assert_ne!(replacement, reviewed);
+
+    assert_eq!(
+        t.client
+            .try_approve_restructure(&t.admin, &loan_id, &reviewed),
+        Err(Ok(CreditLineError::RestructureChanged))
+    );
+    assert_eq!(t.client.get_loan(&loan_id).restructured_at, None);
+
+    assert!(t
+        .client
+        .approve_restructure(&t.admin, &loan_id, &replacement));
+    assert_eq!(t.client.get_loan(&loan_id).repayment_schedule.len(), 3);
+}
+
+#[test]
+fn test_restructure_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+    env.ledger().set_timestamp(10000 + YEAR / 4);
+
+    assert_eq!(
+        t.client
+            .try_propose_restructure(&t.user, &loan_id, &restructured_schedule(&env, 400)),
+        Err(Ok(CreditLineError::ScheduleBelowBalance))
+    );
+    assert_eq!(
+        t.client.try_propose_restructure(
+            &Address::generate(&env),
+            &loan_id,
+            &restructured_schedule(&env, 420)
+        ),
+        Err(Ok(CreditLineError::MissingRole))
+    );
+    assert_eq!(
+        t.client
+            .try_propose_restructure(&t.user, &loan_id, &soroban_sdk::Vec::new(&env)),
+        Err(Ok(CreditLineError::InvalidInstallmentCount))
+    );
+
+    // A loan settled while the proposal is pending can no longer be restructured
+    let proposal_id =
+        t.client
+            .propose_restructure(&t.user, &loan_id, &restructured_schedule(&env, 415));
+    t.client.payoff_loan(&t.user, &loan_id);
+    assert_eq!(
+        t.client
+            .try_approve_restructure(&t.admin, &loan_id, &proposal_id),
+        Err(Ok(CreditLineError::LoanNotActive))
+    );
+}
+
+// Pause tests
+
+#[test]
+fn test_pause_blocks_every_group() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    t.client.pause(&t.admin);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("PAUSECHG"), t.admin.clone()).into_val(&env),
+                (0u32, PAUSE_ALL, 10000u64).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(t.client.get_paused(), PAUSE_ALL);
+
+    assert_eq!(
+        t.client
+            .try_create_loan(&t.user, &t.merchant, &1000, &200, &2, &YEAR),
+        Err(Ok(CreditLineError::ContractPaused.into()))
+    );
+    assert_eq!(
+        t.client.try_repay_loan(&t.user, &loan_id, &100),
+        Err(Ok(CreditLineError::ContractPaused))
+    );
+    assert_eq!(
+        t.client.try_payoff_loan(&t.user, &loan_id),
+        Err(Ok(CreditLineError::ContractPaused))
+    );
+    assert_eq!(
+        t.client.try_refund_loan(&t.merchant, &loan_id, &100),
+        Err(Ok(CreditLineError::ContractPaused))
+    );
+    assert_eq!(
+        t.client.try_assess_late_fees(&loan_id),
+        Err(Ok(CreditLineError::ContractPaused))
+    );
+    assert_eq!(
+        t.client.try_mark_defaulted(&t.admin, &loan_id),
+        Err(Ok(CreditLineError::ContractPaused))
+    );
+
+    // Views still work while paused
+    assert_eq!(t.client.get_loan(&loan_id).status, LoanStatus::Active);
+
+    t.client.unpause(&t.admin);
+    assert_eq!(t.client.get_paused(), 0);
+    t.client.repay_loan(&t.user, &loan_id, &100);
+}
+
+#[test]
+fn test_pause_origination_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let loan_id = create_interest_loan(&env, &t);
+
+    t.client.set_paused(&t.admin, &PAUSE_ORIGINATION);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(10000 + YEAR, 800));
+    assert_eq!(
+        t.client
+            .try_create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule),
+        Err(Ok(CreditLineError::ContractPaused.into()))
+    );
+
+    // Repayments are still accepted
+    assert_eq!(
+        t.client.repay_loan(&t.user, &loan_id, &100),
+        LoanStatus::Active
+    );
+
+    // Unknown bits are dropped
+    t.client.set_paused(&t.admin, &(PAUSE_REPAYMENT | 1 << 31));
+    assert_eq!(t.client.get_paused(), PAUSE_REPAYMENT);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")] // MissingRole
+fn test_pause_not_pauser_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    t.client.pause(&t.user);
+}
+
+// Role tests
+
+#[test]
+fn test_grant_and_revoke_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let pauser = Address::generate(&env);
+
+    assert!(t.client.has_role(&Role::Pauser, &t.admin));
+    assert!(!t.client.has_role(&Role::Pauser, &pauser));
+
+    t.client.grant_role(&t.admin, &Role::Pauser, &pauser);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("ROLEGRNT"), Role::Pauser, pauser.clone()).into_val(&env),
+                t.admin.clone().into_val(&env),
+            )
+        ]
+    );
+    assert!(t.client.has_role(&Role::Pauser, &pauser));
+
+    t.client.pause(&pauser);
+    t.client.unpause(&pauser);
+
+    t.client.revoke_role(&t.admin, &Role::Pauser, &pauser);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("ROLERVKD"), Role::Pauser, pauser.clone()).into_val(&env),
+                t.admin.clone().into_val(&env),
+            )
+        ]
+    );
+    assert!(!t.client.has_role(&Role::Pauser, &pauser));
+    assert_eq!(
+        t.client.try_pause(&pauser),
+        Err(Ok(CreditLineError::MissingRole.into()))
+    );
+}
+
+#[test]
+fn test_roles_are_scoped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let manager = Address::generate(&env);
+    t.client.grant_role(&t.admin, &Role::RiskManager, &manager);
+
+    // A risk manager tunes parameters but can't pause or change addresses
+    apply_change(&env, &t.client, &manager, &ParamChange::PayoffRebate(5_000));
+    assert_eq!(t.client.get_payoff_rebate(), 5_000);
+    assert_eq!(
+        t.client.try_pause(&manager),
+        Err(Ok(CreditLineError::MissingRole.into()))
+    );
+    assert_eq!(
+        t.client
+            .try_queue_change(&manager, &ParamChange::Token(t.token.address.clone())),
+        Err(Ok(CreditLineError::NotAdmin))
+    );
+    assert_eq!(
+        t.client.try_grant_role(&manager, &Role::Pauser, &manager),
+        Err(Ok(CreditLineError::NotAdmin.into()))
+    );
+
+    // An address holding the Admin role can manage roles too
+    let co_admin = Address::generate(&env);
+    t.client.grant_role(&t.admin, &Role::Admin, &co_admin);
+    t.client.grant_role(&co_admin, &Role::Pauser, &manager);
+    assert!(t.client.has_role(&Role::Pauser, &manager));
+}
+
+#[test]
+fn test_mark_defaulted_requires_collector() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    let mut schedule = soroban_sdk::Vec::new(&env);
+    schedule.push_back(installment(11000, 800));
+    let loan_id = t
+        .client
+        .create_loan_with_schedule(&t.user, &t.merchant, &1000, &200, &schedule);
+    env.ledger().set_timestamp(12000);
+
+    let collector = Address::generate(&env);
+    assert_eq!(
+        t.client.try_mark_defaulted(&collector, &loan_id),
+        Err(Ok(CreditLineError::MissingRole))
+    );
+
+    t.client.grant_role(&t.admin, &Role::Collector, &collector);
+    t.client.mark_defaulted(&collector, &loan_id);
+    assert_eq!(t.client.get_loan(&loan_id).status, LoanStatus::Defaulted);
+}
+
+// Timelock tests
+
+#[test]
+fn test_queue_and_execute_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let new_pool = Address::generate(&env);
+    let change = ParamChange::LiquidityPool(new_pool.clone());
+    let delay = t.client.get_timelock_delay();
+    assert_eq!(delay, 2 * 24 * 60 * 60);
+
+    let change_id = t.client.queue_change(&t.admin, &change);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("CHGQUEUED"), change_id).into_val(&env),
+                (t.admin.clone(), change.clone(), 10000 + delay).into_val(&env),
+            )
+        ]
+    );
+
+    let queued = t.client.get_queued_change(&change_id).unwrap();
+    assert_eq!(queued.change, change);
+    assert_eq!(queued.queued_at, 10000);
+    assert_eq!(queued.eta, 10000 + delay);
+
+    // The current pool keeps funding loans until the delay has passed
+    env.ledger().set_timestamp(10000 + delay - 1);
+    assert_eq!(
+        t.client.try_execute_change(&t.admin, &change_id),
+        Err(Ok(CreditLineError::TimelockNotExpired))
+    );
+
+    env.ledger().set_timestamp(10000 + delay);
+    t.client.execute_change(&t.admin, &change_id);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("CHGEXECD"), change_id).into_val(&env),
+                (t.admin.clone(), change, 10000 + delay).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(t.client.get_queued_change(&change_id), None);
+
+    env.as_contract(&t.contract_id, || {
+        assert_eq!(storage::get_liquidity_pool(&env), Some(new_pool));
+    });
+
+    // A change can only be executed once
+    assert_eq!(
+        t.client.try_execute_change(&t.admin, &change_id),
+        Err(Ok(CreditLineError::ChangeNotFound))
+    );
+}
+
+#[test]
+fn test_cancel_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let change_id = t
+        .client
+        .queue_change(&t.admin, &ParamChange::PayoffRebate(2_500));
+
+    t.client.cancel_change(&t.admin, &change_id);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("CHGCNCLD"), change_id).into_val(&env),
+                (t.admin.clone(), 10000u64).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(t.client.get_queued_change(&change_id), None);
+
+    env.ledger()
+        .set_timestamp(10000 + t.client.get_timelock_delay());
+    assert_eq!(
+        t.client.try_execute_change(&t.admin, &change_id),
+        Err(Ok(CreditLineError::ChangeNotFound))
+    );
+    assert_eq!(
+        t.client.try_cancel_change(&t.admin, &change_id),
+        Err(Ok(CreditLineError::ChangeNotFound))
+    );
+    assert_eq!(t.client.get_payoff_rebate(), 10_000);
+}
+
+#[test]
+fn test_queue_change_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let manager = Address::generate(&env);
+    t.client.grant_role(&t.admin, &Role::RiskManager, &manager);
+
+    // Risk managers queue risk parameters but not contract addresses
+    apply_change(&env, &t.client, &manager, &ParamChange::PayoffRebate(2_500));
+    assert_eq!(t.client.get_payoff_rebate(), 2_500);
+    assert_eq!(
+        t.client.try_queue_change(
+            &manager,
+            &ParamChange::ReputationContract(Address::generate(&env))
+        ),
+        Err(Ok(CreditLineError::NotAdmin))
+    );
+    assert_eq!(
+        t.client
+            .try_queue_change(&manager, &ParamChange::TimelockDelay(0)),
+        Err(Ok(CreditLineError::NotAdmin))
+    );
+
+    // Only a risk manager can cancel a queued risk parameter change
+    let change_id = t
+        .client
+        .queue_change(&manager, &ParamChange::PayoffRebate(0));
+    assert_eq!(
+        t.client.try_cancel_change(&t.user, &change_id),
+        Err(Ok(CreditLineError::MissingRole))
+    );
+    t.client.cancel_change(&manager, &change_id);
+}
+
+#[test]
+fn test_change_timelock_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+
+    assert_eq!(
+        t.client
+            .try_queue_change(&t.admin, &ParamChange::TimelockDelay(30 * 24 * 60 * 60 + 1)),
+        Err(Ok(CreditLineError::InvalidTimelockDelay))
+    );
+
+    // The delay itself is timelocked by the current delay
+    apply_change(&env, &t.client, &t.admin, &ParamChange::TimelockDelay(600));
+    assert_eq!(t.client.get_timelock_delay(), 600);
+
+    let change_id = t
+        .client
+        .queue_change(&t.admin, &ParamChange::PayoffRebate(0));
+    assert_eq!(t.client.get_queued_change(&change_id).unwrap().eta, 10600);
+}
+
+// Governance tests
+
+/// Configures a 2-of-3 signer set and returns the signers
+fn setup_signers(env: &Env, t: &LoanTest) -> (Address, Address, Address) {
+    let signers = (
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    );
+    t.client.set_signers(
+        &t.admin,
+        &vec![env, signers.0.clone(), signers.1.clone(), signers.2.clone()],
+        &2,
+    );
+    signers
+}
+
+#[test]
+fn test_governance_grants_role_after_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let (alice, bob, carol) = setup_signers(&env, &t);
+    let pauser = Address::generate(&env);
+
+    // Role grants no longer bypass the signers
+    assert_eq!(
+        t.client.try_grant_role(&t.admin, &Role::Pauser, &pauser),
+        Err(Ok(CreditLineError::GovernanceRequired.into()))
+    );
+    assert_eq!(
+        t.client.try_propose_action(
+            &t.admin,
+            &GovAction::GrantRole(Role::Pauser, pauser.clone())
+        ),
+        Err(Ok(CreditLineError::NotSigner))
+    );
+
+    let action = GovAction::GrantRole(Role::Pauser, pauser.clone());
+    let proposal_id = t.client.propose_action(&alice, &action);
+    assert!(!t.client.has_role(&Role::Pauser, &pauser));
+    assert_eq!(
+        t.client.try_approve(&alice, &proposal_id),
+        Err(Ok(CreditLineError::AlreadyApproved))
+    );
+
+    assert!(t.client.approve(&bob, &proposal_id));
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("ROLEGRNT"), Role::Pauser, pauser.clone()).into_val(&env),
+                t.contract_id.clone().into_val(&env),
+            )
+        ]
+    );
+    assert!(t.client.has_role(&Role::Pauser, &pauser));
+
+    let proposal = t.client.get_proposal(&proposal_id).unwrap();
+    assert!(proposal.executed);
+    assert_eq!(proposal.action, action);
+    assert_eq!(proposal.approvals, vec![&env, alice, bob]);
+
+    assert_eq!(
+        t.client.try_approve(&carol, &proposal_id),
+        Err(Ok(CreditLineError::ProposalExecuted))
+    );
+    assert_eq!(
+        t.client.try_approve(&carol, &99),
+        Err(Ok(CreditLineError::ProposalNotFound))
+    );
+}
+
+#[test]
+fn test_governance_changes_admin_and_signers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let (alice, bob, carol) = setup_signers(&env, &t);
+
+    // Direct admin transfers and signer set changes are closed
+    assert_eq!(
+        t.client.try_propose_admin(&Address::generate(&env)),
+        Err(Ok(CreditLineError::GovernanceRequired.into()))
+    );
+    assert_eq!(
+        t.client
+            .try_set_signers(&t.admin, &vec![&env, alice.clone()], &1),
+        Err(Ok(CreditLineError::GovernanceRequired))
+    );
+
+    let new_admin = Address::generate(&env);
+    let proposal_id = t
+        .client
+        .propose_action(&bob, &GovAction::SetAdmin(new_admin.clone()));
+
+    // Dropping bob from the signers voids his approval of the pending proposal
+    let rotation = t.client.propose_action(
+        &alice,
+        &GovAction::SetSigners(vec![&env, alice.clone(), carol.clone()], 2),
+    );
+    assert!(t.client.approve(&carol, &rotation));
+    assert_eq!(
+        t.client.get_signers(),
+        vec![&env, alice.clone(), carol.clone()]
+    );
+    assert_eq!(t.client.get_threshold(), 2);
+
+    assert!(!t.client.approve(&alice, &proposal_id));
+    assert_eq!(t.client.get_admin(), t.admin);
+    assert!(t.client.approve(&carol, &proposal_id));
+    assert_eq!(t.client.get_admin(), new_admin);
+
+    assert_eq!(
+        t.client
+            .try_propose_action(&bob, &GovAction::SetAdmin(bob.clone())),
+        Err(Ok(CreditLineError::NotSigner))
+    );
+}
+
+#[test]
+fn test_governance_closes_pending_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let new_admin = Address::generate(&env);
+    t.client.propose_admin(&new_admin);
+    setup_signers(&env, &t);
+
+    // A transfer started before the signers were set can't bypass them
+    assert_eq!(
+        t.client.try_accept_admin(),
+        Err(Ok(CreditLineError::GovernanceRequired))
+    );
+    assert_eq!(t.client.get_admin(), t.admin);
+}
+
+#[test]
+fn test_set_signers_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    assert_eq!(t.client.get_threshold(), 0);
+
+    for (signers, threshold) in [
+        (vec![&env, alice.clone(), bob.clone()], 0),
+        (vec![&env, alice.clone(), bob.clone()], 3),
+        (vec![&env, alice.clone(), alice.clone()], 2),
+    ] {
+        assert_eq!(
+            t.client.try_set_signers(&t.admin, &signers, &threshold),
+            Err(Ok(CreditLineError::InvalidSignerSet))
+        );
+    }
+    assert_eq!(
+        t.client
+            .try_set_signers(&t.user, &vec![&env, alice.clone()], &1),
+        Err(Ok(CreditLineError::NotAdmin))
+    );
+
+    // A single signer runs actions as soon as they are proposed
+    t.client
+        .set_signers(&t.admin, &vec![&env, alice.clone()], &1);
+    assert_eq!(
+        t.client
+            .try_propose_action(&alice, &GovAction::SetSigners(vec![&env, alice.clone()], 2)),
+        Err(Ok(CreditLineError::InvalidSignerSet))
+    );
+    let proposal_id = t
+        .client
+        .propose_action(&alice, &GovAction::RevokeRole(Role::Admin, bob.clone()));
+    assert!(t.client.get_proposal(&proposal_id).unwrap().executed);
+}
+
+// Smallest WASM the host accepts: no code, only the env meta section for protocol 22
+const EMPTY_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x00, 0x1e, 0x11, b'c', b'o', b'n', b't', b'r',
+    b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0', 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn test_upgrade_replaces_code() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    assert_eq!(t.client.get_wasm_hash(), Some(deployed_wasm_hash(&env)));
+    let new_wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(&env, EMPTY_WASM));
+
+    t.client.upgrade(&t.admin, &new_wasm_hash);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("UPGRADED"),).into_val(&env),
+                (Some(deployed_wasm_hash(&env)), new_wasm_hash.clone()).into_val(&env),
+            )
+        ]
+    );
+    let stored_hash = env.as_contract(&t.contract_id, || common::upgrade::get_wasm_hash(&env));
+    assert_eq!(stored_hash, Some(new_wasm_hash));
+}
+
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    let new_wasm_hash = soroban_sdk::BytesN::from_array(&env, &[7; 32]);
+    assert_eq!(
+        t.client.try_upgrade(&t.user, &new_wasm_hash),
+        Err(Ok(CreditLineError::NotAdmin.into()))
+    );
+
+    // Once signers are configured, upgrades go through an `Upgrade` proposal
+    let alice = Address::generate(&env);
+    t.client
+        .set_signers(&t.admin, &vec![&env, alice.clone()], &1);
+    assert_eq!(
+        t.client.try_upgrade(&t.admin, &new_wasm_hash),
+        Err(Ok(CreditLineError::GovernanceRequired.into()))
+    );
+}
+
+#[test]
+fn test_migrate_runs_once_per_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup_loan_test(&env);
+    assert_eq!(t.client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        t.client.try_migrate(),
+        Err(Ok(CreditLineError::AlreadyMigrated))
+    );
+
+    // Deployed before versions were tracked
+    env.as_contract(&t.contract_id, || {
+        env.storage()
+            .instance()
+            .remove(&common::upgrade::VERSION_KEY);
+    });
+    assert_eq!(t.client.get_version(), 0);
+
+    assert_eq!(t.client.migrate(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("MIGRATED"),).into_val(&env),
+                (0u32, crate::types::CONTRACT_VERSION).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(t.client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        t.client.try_migrate(),
+        Err(Ok(CreditLineError::AlreadyMigrated))
+    );
 }
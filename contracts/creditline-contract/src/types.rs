@@ -1,4 +1,38 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+// Storage keys for per-loan entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Loan(u64),
+    BorrowerLoanCount(Address),
+    BorrowerLoan(Address, u32),
+    MerchantLoanCount(Address),
+    MerchantLoan(Address, u32),
+    Exposure(Address),
+    Restructure(u64),
+    ScheduleHistory(u64),
+    QueuedChange(u64),
+}
+
+// Loan ID index kept per borrower and per merchant, one entry per slot
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoanIndex {
+    Borrower(Address),
+    Merchant(Address),
+}
+
+// Score hook run on the Reputation contract as a loan progresses
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReputationHook {
+    OnTime,
+    Early,
+    Completion,
+    Late,
+    Missed,
+    Restructure,
+    Slash,
+}
 
 // Loan status enum
 #[contracttype]
@@ -7,14 +41,77 @@ pub enum LoanStatus {
     Active,
     Paid,
     Defaulted,
+    Cancelled, // Fully refunded by the merchant before any repayment
+    Refunded,  // Fully refunded by the merchant after the borrower repaid part of it
 }
 
 // Repayment installment structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RepaymentInstallment {
-    pub due_date: u64, // Unix timestamp
-    pub amount: i128,  // Amount due for this installment
+    pub due_date: u64,     // Unix timestamp
+    pub amount: i128,      // Amount due for this installment (principal + interest)
+    pub principal: i128,   // Principal portion of the installment
+    pub interest: i128,    // Interest accrued on the principal portion until the due date
+    pub paid_amount: i128, // Amount repaid so far towards this installment
+    pub late: bool,        // Late fee assessed after the grace period
+    pub missed: bool,      // Still unpaid after the missed payment period
+}
+
+// Late fee charged once per installment unpaid after its grace period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateFeeConfig {
+    pub grace_period: u64, // Seconds after the due date before a fee applies
+    pub flat_fee: i128,    // Fixed fee per late installment
+    pub percent_bps: u32,  // Fee in basis points of the installment's unpaid amount
+}
+
+// Credit tier assigned from the borrower's reputation score
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CreditTier {
+    Excellent,
+    Good,
+    Fair,
+    Poor,
+    VeryPoor,
+}
+
+// Pricing and limit for a credit tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierConfig {
+    pub tier: CreditTier,
+    pub min_score: u32,   // Lowest reputation score in this tier
+    pub apr_bps: u32,     // Annual interest rate in basis points
+    pub max_credit: i128, // Maximum amount financed by the pool
+}
+
+// Bounds on the repayment schedules accepted by create_loan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleLimits {
+    pub min_installments: u32,
+    pub max_installments: u32,
+    pub min_tenor: u64, // Seconds from creation to the last due date
+    pub max_tenor: u64,
+}
+
+// Open loans and unpaid principal of a borrower
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BorrowerExposure {
+    pub active_loans: u32,
+    pub outstanding_principal: i128,
+}
+
+// Caps on a borrower's exposure, checked when a loan is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExposureLimits {
+    pub max_active_loans: u32,
+    pub max_outstanding: i128, // Maximum unpaid principal across active loans
 }
 
 // Loan data structure
@@ -30,8 +127,211 @@ pub struct Loan {
     pub repayment_schedule: soroban_sdk::Vec<RepaymentInstallment>,
     pub status: LoanStatus,
     pub created_at: u64, // Unix timestamp
+    pub tier: CreditTier,
+    pub apr_bps: u32,                 // Annual interest rate in basis points
+    pub principal: i128,              // Amount financed by the pool
+    pub interest_amount: i128,        // Total interest over the full schedule
+    pub accrued_interest: i128,       // Interest accrued as of the last update
+    pub principal_paid: i128,         // Principal repaid so far
+    pub interest_paid: i128,          // Interest repaid so far
+    pub fees: i128,                   // Fees owed on top of the schedule
+    pub restructured_at: Option<u64>, // Time the current schedule replaced an earlier one
+    pub capitalized_interest: i128,   // Interest accrued before the latest restructure
+}
+
+// Installment layout of the first release, before interest and payments were tracked
+// Frozen so loans in the legacy instance map can still be decoded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyInstallment {
+    pub due_date: u64,
+    pub amount: i128,
+}
+
+// Loan layout of the first release, kept in the legacy `LOANS` instance map
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyLoan {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub merchant: Address,
+    pub total_amount: i128,
+    pub guarantee_amount: i128,
+    pub remaining_balance: i128,
+    pub repayment_schedule: Vec<LegacyInstallment>,
+    pub status: LoanStatus,
+    pub created_at: u64,
+}
+
+impl LegacyLoan {
+    /// Convert to the current layout
+    /// First release loans were not priced: they carry no interest, keep the `Fair` tier and
+    /// owe at most the part of the purchase not covered by the guarantee
+    pub fn into_loan(self, env: &Env) -> Loan {
+        let principal = (self.total_amount - self.guarantee_amount).max(0);
+        let remaining_balance = self.remaining_balance.clamp(0, principal);
+
+        let mut repayment_schedule = Vec::new(env);
+        for installment in self.repayment_schedule.iter() {
+            repayment_schedule.push_back(RepaymentInstallment {
+                due_date: installment.due_date,
+                amount: installment.amount,
+                principal: installment.amount,
+                interest: 0,
+                paid_amount: 0,
+                late: false,
+                missed: false,
+            });
+        }
+
+        Loan {
+            loan_id: self.loan_id,
+            borrower: self.borrower,
+            merchant: self.merchant,
+            total_amount: self.total_amount,
+            guarantee_amount: self.guarantee_amount,
+            remaining_balance,
+            repayment_schedule,
+            status: self.status,
+            created_at: self.created_at,
+            tier: CreditTier::Fair,
+            apr_bps: 0,
+            principal,
+            interest_amount: 0,
+            accrued_interest: 0,
+            principal_paid: principal - remaining_balance,
+            interest_paid: 0,
+            fees: 0,
+            restructured_at: None,
+            capitalized_interest: 0,
+        }
+    }
+}
+
+// Amount needed to settle a loan at the current ledger time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanPayoff {
+    pub principal: i128,
+    pub interest: i128,
+    pub fees: i128,
+    pub total: i128,
+}
+
+// Replacement schedule waiting for approval by the borrower and an admin
+// Installment `amount` is the full amount due, the contract splits out principal and interest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RestructureProposal {
+    pub proposal_id: u64, // Changes with every new proposal, approvals name the one reviewed
+    pub schedule: soroban_sdk::Vec<RepaymentInstallment>,
+    pub proposed_at: u64,
+    pub borrower_approved: bool,
+    pub admin_approved: bool,
+}
+
+// Schedule replaced by a restructure, kept for history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleRevision {
+    pub replaced_at: u64,
+    pub schedule: soroban_sdk::Vec<RepaymentInstallment>,
+}
+
+// How a merchant refund was split between the pool and the borrower
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanRefund {
+    pub to_pool: i128,
+    pub to_borrower: i128,
+    pub guarantee_returned: i128,
+    pub interest_waived: i128,
+}
+
+// Change to an external contract address or a risk parameter, applied after the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParamChange {
+    ReputationContract(Address),
+    MerchantRegistry(Address),
+    LiquidityPool(Address), // Only while no loans are active
+    Token(Address),         // Only while no loans are active
+    CreditTiers(Vec<TierConfig>),
+    ExposureLimits(ExposureLimits), // Default limits
+    TierExposureLimits(CreditTier, ExposureLimits),
+    ClearExposureLimits(CreditTier),
+    LateFeeConfig(LateFeeConfig),
+    ScheduleLimits(ScheduleLimits),
+    PayoffRebate(u32),
+    TimelockDelay(u64),
+}
+
+// Change waiting for its timelock delay to pass
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedChange {
+    pub change: ParamChange,
+    pub queued_at: u64,
+    pub eta: u64, // Earliest time the change can be executed
 }
 
 // Constants
+pub const CONTRACT_VERSION: u32 = 1; // Storage layout version of this code, see `migrate`
 pub const MIN_GUARANTEE_PERCENT: i128 = 20; // 20% minimum guarantee
 pub const MIN_REPUTATION_THRESHOLD: u32 = 50; // Minimum reputation score required
+pub const EARLY_PAYMENT_WINDOW: u64 = 7 * 24 * 60 * 60; // Paid 7+ days before due date is early
+pub const MISSED_PAYMENT_PERIOD: u64 = 7 * 24 * 60 * 60; // Unpaid 7+ days after due date is missed
+pub const DEFAULT_GRACE_PERIOD: u64 = 3 * 24 * 60 * 60;
+pub const DEFAULT_LATE_FEE_BPS: u32 = 0;
+pub const DEFAULT_MIN_INSTALLMENTS: u32 = 1;
+pub const DEFAULT_MAX_INSTALLMENTS: u32 = 12;
+pub const DEFAULT_MIN_TENOR: u64 = 7 * 24 * 60 * 60;
+pub const DEFAULT_MAX_TENOR: u64 = 365 * 24 * 60 * 60;
+// Persistent entry TTL (in ledgers)
+pub const DAY_IN_LEDGERS: u32 = 17280;
+pub const LOAN_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const LOAN_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+
+pub const DEFAULT_MAX_ACTIVE_LOANS: u32 = 3;
+pub const DEFAULT_MAX_OUTSTANDING: i128 = 10_000 * STABLECOIN_UNIT;
+pub const DEFAULT_PAYOFF_REBATE_BPS: u32 = 10_000; // Waive all unaccrued interest
+pub const MAX_PAGE_SIZE: u32 = 15; // Loans per paginated query, each reads an index slot and a loan
+pub const DEFAULT_TIMELOCK_DELAY: u64 = 2 * 24 * 60 * 60;
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60;
+
+// Pause flags, each pausing one group of entrypoints
+pub const PAUSE_ORIGINATION: u32 = 1 << 0; // create_loan, create_loan_with_schedule
+pub const PAUSE_REPAYMENT: u32 = 1 << 1; // repay_loan, payoff_loan
+pub const PAUSE_REFUND: u32 = 1 << 2; // refund_loan
+pub const PAUSE_COLLECTION: u32 = 1 << 3; // assess_late_fees, mark_defaulted
+pub const PAUSE_RESTRUCTURE: u32 = 1 << 4; // propose_restructure, approve_restructure
+pub const PAUSE_ALL: u32 =
+    PAUSE_ORIGINATION | PAUSE_REPAYMENT | PAUSE_REFUND | PAUSE_COLLECTION | PAUSE_RESTRUCTURE;
+
+pub const MAX_APR_BPS: u32 = 10_000; // 100% APR
+pub const BPS_DENOMINATOR: i128 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+pub const STABLECOIN_UNIT: i128 = 10_000_000; // 7 decimals
+pub const DEFAULT_LATE_FEE: i128 = 5 * STABLECOIN_UNIT;
+
+/// Default tier table, ordered from the highest to the lowest score
+pub fn default_credit_tiers(env: &Env) -> Vec<TierConfig> {
+    let tiers = [
+        (CreditTier::Excellent, 90, 500, 5_000),
+        (CreditTier::Good, 75, 700, 2_000),
+        (CreditTier::Fair, 60, 900, 1_000),
+        (CreditTier::Poor, 40, 1_250, 500),
+        (CreditTier::VeryPoor, 0, 1_500, 0),
+    ];
+
+    let mut table = Vec::new(env);
+    for (tier, min_score, apr_bps, max_credit) in tiers {
+        table.push_back(TierConfig {
+            tier,
+            min_score,
+            apr_bps,
+            max_credit: max_credit * STABLECOIN_UNIT,
+        });
+    }
+    table
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+// Cross-contract interfaces of the TrustUp contracts
+// Each contract implements its trait and callers use the generated client, so both sides
+// are checked against these signatures at compile time
+
+// Module imports
+mod liquidity_pool;
+mod merchant_registry;
+mod reputation;
+
+// Re-export clients for external use
+pub use liquidity_pool::{LiquidityPoolClient, LiquidityPoolError, LiquidityPoolInterface};
+pub use merchant_registry::{MerchantRegistryClient, MerchantRegistryInterface};
+pub use reputation::{ReputationClient, ReputationInterface};
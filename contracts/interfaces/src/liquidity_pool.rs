@@ -0,0 +1,51 @@
+use soroban_sdk::{contractclient, contracterror, Address, Env};
+
+// Error types for the liquidity pool contract, shared so callers can match on them
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LiquidityPoolError {
+    NotAdmin = 1,
+    NotCreditLine = 2,
+    InvalidAmount = 3,
+    InsufficientShares = 4,
+    InsufficientLiquidity = 5,
+    PoolEmpty = 6,
+    Overflow = 7,
+    Underflow = 8,
+    NoPendingAdmin = 9,
+    AlreadyMigrated = 10,
+}
+
+/// Liquidity Pool entrypoints called by the CreditLine contract
+/// Loan hooks require `creditline` to be the CreditLine registered in the pool
+#[contractclient(name = "LiquidityPoolClient")]
+pub trait LiquidityPoolInterface {
+    /// Pay a merchant for a new loan
+    fn fund_loan(
+        env: Env,
+        creditline: Address,
+        merchant: Address,
+        amount: i128,
+    ) -> Result<(), LiquidityPoolError>;
+
+    /// Record a repayment already transferred to the pool
+    fn receive_repayment(
+        env: Env,
+        creditline: Address,
+        principal: i128,
+        interest: i128,
+    ) -> Result<(), LiquidityPoolError>;
+
+    /// Write off the principal of a defaulted loan
+    /// `recovered` is the forfeited guarantee already transferred to the pool
+    fn record_default(
+        env: Env,
+        creditline: Address,
+        principal_lost: i128,
+        recovered: i128,
+    ) -> Result<(), LiquidityPoolError>;
+
+    /// Get the liquidity that is not lent out
+    fn get_available_liquidity(env: Env) -> i128;
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Merchant Registry entrypoints called by other contracts
+#[contractclient(name = "MerchantRegistryClient")]
+pub trait MerchantRegistryInterface {
+    /// Check if a merchant is registered and active
+    fn is_active(env: Env, merchant: Address) -> bool;
+
+    /// Get the address a merchant's loans are paid out to
+    fn get_payout_address(env: Env, merchant: Address) -> Address;
+}
@@ -0,0 +1,30 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Reputation contract entrypoints called by other contracts
+/// Score hooks require `updater` to hold the `Updater` role
+#[contractclient(name = "ReputationClient")]
+pub trait ReputationInterface {
+    /// Get the reputation score for a user
+    fn get_score(env: Env, user: Address) -> u32;
+
+    /// Apply the default penalty, clamped at 0
+    fn slash(env: Env, updater: Address, user: Address);
+
+    /// Reward an installment paid by its due date, clamped at 100
+    fn reward_on_time(env: Env, updater: Address, user: Address);
+
+    /// Reward an installment paid well before its due date, clamped at 100
+    fn reward_early(env: Env, updater: Address, user: Address);
+
+    /// Reward a fully repaid loan, clamped at 100
+    fn reward_completion(env: Env, updater: Address, user: Address);
+
+    /// Penalize an installment past its grace period, clamped at 0
+    fn penalize_late(env: Env, updater: Address, user: Address);
+
+    /// Penalize an installment still unpaid a week after its due date, clamped at 0
+    fn penalize_missed(env: Env, updater: Address, user: Address);
+
+    /// Penalize a loan rescheduled instead of defaulting, clamped at 0
+    fn penalize_restructure(env: Env, updater: Address, user: Address);
+}
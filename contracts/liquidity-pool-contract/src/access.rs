@@ -0,0 +1,21 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::errors::LiquidityPoolError;
+use crate::storage;
+
+/// Require that the given address is the admin, otherwise panic with NotAdmin error
+pub fn require_admin(env: &Env, caller: &Address) {
+    let admin = storage::get_admin(env);
+
+    if caller != &admin {
+        panic_with_error!(env, LiquidityPoolError::NotAdmin);
+    }
+}
+
+/// Require that the given address is the registered CreditLine contract,
+/// otherwise panic with NotCreditLine error
+pub fn require_creditline(env: &Env, caller: &Address) {
+    if storage::get_creditline(env).as_ref() != Some(caller) {
+        panic_with_error!(env, LiquidityPoolError::NotCreditLine);
+    }
+}
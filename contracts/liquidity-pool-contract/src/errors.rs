@@ -0,0 +1,2 @@
+// Error types for the liquidity pool contract, defined with its interface so callers share them
+pub use interfaces::LiquidityPoolError;
@@ -0,0 +1,82 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+// Event topics
+const LIQUIDITY_DEPOSITED: Symbol = symbol_short!("LIQDEPOS");
+const LIQUIDITY_WITHDRAWN: Symbol = symbol_short!("LIQWDRAW");
+const LOAN_FUNDED: Symbol = symbol_short!("LOANFUND");
+const REPAYMENT_RECEIVED: Symbol = symbol_short!("REPAYRCV");
+const DEFAULT_RECORDED: Symbol = symbol_short!("LOSSRCRD");
+const ADMIN_PROPOSED: Symbol = symbol_short!("ADMINPROP");
+const ADMIN_CHANGED: Symbol = symbol_short!("ADMINCHGD");
+const ADMIN_TRANSFER_CANCELLED: Symbol = symbol_short!("ADMINCNCL");
+
+/// Emit a liquidity deposited event
+pub fn emit_liquidity_deposited(env: &Env, provider: &Address, amount: i128, shares: i128) {
+    env.events().publish(
+        (LIQUIDITY_DEPOSITED, provider),
+        (amount, shares, env.ledger().timestamp()),
+    );
+}
+
+/// Emit a liquidity withdrawn event
+pub fn emit_liquidity_withdrawn(env: &Env, provider: &Address, amount: i128, shares: i128) {
+    env.events().publish(
+        (LIQUIDITY_WITHDRAWN, provider),
+        (amount, shares, env.ledger().timestamp()),
+    );
+}
+
+/// Emit a loan funded event
+pub fn emit_loan_funded(env: &Env, merchant: &Address, amount: i128) {
+    env.events()
+        .publish((LOAN_FUNDED, merchant), (amount, env.ledger().timestamp()));
+}
+
+/// Emit a repayment received event with the resulting share value
+pub fn emit_repayment_received(env: &Env, principal: i128, interest: i128, share_value: i128) {
+    env.events().publish(
+        (REPAYMENT_RECEIVED,),
+        (principal, interest, share_value, env.ledger().timestamp()),
+    );
+}
+
+/// Emit a default recorded event with the resulting share value
+pub fn emit_default_recorded(env: &Env, principal_lost: i128, recovered: i128, share_value: i128) {
+    env.events().publish(
+        (DEFAULT_RECORDED,),
+        (
+            principal_lost,
+            recovered,
+            share_value,
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit an admin proposed event when a transfer starts
+pub fn emit_admin_proposed(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_PROPOSED, admin),
+        (pending_admin.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit an admin changed event when the proposed admin accepts
+pub fn emit_admin_changed(env: &Env, old_admin: &Address, new_admin: &Address) {
+    env.events().publish(
+        (ADMIN_CHANGED,),
+        (
+            old_admin.clone(),
+            new_admin.clone(),
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit an admin transfer cancelled event
+pub fn emit_admin_transfer_cancelled(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_TRANSFER_CANCELLED, admin),
+        (pending_admin.clone(), env.ledger().timestamp()),
+    );
+}
@@ -0,0 +1,419 @@
+#![no_std]
+use common::upgrade;
+use interfaces::LiquidityPoolInterface;
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env};
+
+// Module imports
+mod access;
+mod errors;
+mod events;
+mod storage;
+mod types;
+
+// Re-export types for external use
+pub use errors::LiquidityPoolError;
+
+/// Liquidity Pool contract structure
+#[contract]
+pub struct LiquidityPoolContract;
+
+/// Contract implementation
+#[contractimpl]
+impl LiquidityPoolContract {
+    /// Get the storage version the contract was last migrated to
+    /// Set to this code's version at initialize and bumped by `migrate` after each upgrade
+    pub fn get_version(env: Env) -> u32 {
+        upgrade::get_version(&env)
+    }
+
+    /// Initialize the pool with admin, token, the CreditLine contract allowed to draw funds
+    /// and the deployed code hash
+    /// Can only be called once (when admin is not set)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        creditline: Address,
+        wasm_hash: BytesN<32>,
+    ) {
+        let admin_opt: Option<Address> = env.storage().instance().get(&storage::ADMIN_KEY);
+        if admin_opt.is_some() {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+
+        storage::set_admin(&env, &admin);
+        storage::set_token(&env, &token);
+        storage::set_creditline(&env, &creditline);
+        upgrade::set_version(&env, types::CONTRACT_VERSION);
+        upgrade::set_wasm_hash(&env, &wasm_hash);
+    }
+
+    /// Install new contract code from an uploaded WASM hash (admin only)
+    /// The new code runs from the next call, which should be `migrate`
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        upgrade::upgrade(&env, &new_wasm_hash);
+    }
+
+    /// Bring storage from the stored version up to this code's version
+    /// Anyone can call this after an upgrade, each version bump migrates once.
+    /// Returns the new storage version
+    pub fn migrate(env: Env) -> Result<u32, LiquidityPoolError> {
+        let from_version = upgrade::get_version(&env);
+        if from_version >= types::CONTRACT_VERSION {
+            return Err(LiquidityPoolError::AlreadyMigrated);
+        }
+
+        // Layout changes are applied here, one step per version. Deployments from
+        // before versions were tracked (version 0) share the version 1 layout
+
+        upgrade::finish_migration(&env, from_version, types::CONTRACT_VERSION);
+        Ok(types::CONTRACT_VERSION)
+    }
+
+    /// Get the hash of the installed code, recorded at initialize and on every upgrade
+    pub fn get_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        upgrade::get_wasm_hash(&env)
+    }
+
+    /// Deposit liquidity and receive pool shares
+    /// The first deposit is issued shares 1:1, later deposits proportionally to pool value
+    pub fn deposit(env: Env, provider: Address, amount: i128) -> Result<i128, LiquidityPoolError> {
+        provider.require_auth();
+
+        if amount <= 0 {
+            return Err(LiquidityPoolError::InvalidAmount);
+        }
+
+        let total_shares = storage::get_total_shares(&env);
+        let total_value = Self::total_pool_value(&env)?;
+
+        let shares = if total_shares == 0 {
+            amount
+        } else {
+            if total_value == 0 {
+                return Err(LiquidityPoolError::PoolEmpty);
+            }
+            amount
+                .checked_mul(total_shares)
+                .and_then(|v| v.checked_div(total_value))
+                .ok_or(LiquidityPoolError::Overflow)?
+        };
+
+        if shares <= 0 {
+            return Err(LiquidityPoolError::InvalidAmount);
+        }
+
+        token::Client::new(&env, &storage::get_token(&env)).transfer(
+            &provider,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let provider_shares = storage::read_shares(&env, &provider)
+            .checked_add(shares)
+            .ok_or(LiquidityPoolError::Overflow)?;
+        storage::write_shares(&env, &provider, provider_shares);
+        storage::set_total_shares(
+            &env,
+            total_shares
+                .checked_add(shares)
+                .ok_or(LiquidityPoolError::Overflow)?,
+        );
+        storage::set_available(
+            &env,
+            storage::get_available(&env)
+                .checked_add(amount)
+                .ok_or(LiquidityPoolError::Overflow)?,
+        );
+
+        events::emit_liquidity_deposited(&env, &provider, amount, shares);
+
+        Ok(shares)
+    }
+
+    /// Burn pool shares and withdraw their current value
+    /// Limited by the liquidity that is not lent out
+    pub fn withdraw(env: Env, provider: Address, shares: i128) -> Result<i128, LiquidityPoolError> {
+        provider.require_auth();
+
+        if shares <= 0 {
+            return Err(LiquidityPoolError::InvalidAmount);
+        }
+
+        let provider_shares = storage::read_shares(&env, &provider);
+        if shares > provider_shares {
+            return Err(LiquidityPoolError::InsufficientShares);
+        }
+
+        let total_shares = storage::get_total_shares(&env);
+        let total_value = Self::total_pool_value(&env)?;
+
+        let amount = shares
+            .checked_mul(total_value)
+            .and_then(|v| v.checked_div(total_shares))
+            .ok_or(LiquidityPoolError::Overflow)?;
+
+        let available = storage::get_available(&env);
+        if amount > available {
+            return Err(LiquidityPoolError::InsufficientLiquidity);
+        }
+
+        storage::write_shares(
+            &env,
+            &provider,
+            provider_shares
+                .checked_sub(shares)
+                .ok_or(LiquidityPoolError::Underflow)?,
+        );
+        storage::set_total_shares(
+            &env,
+            total_shares
+                .checked_sub(shares)
+                .ok_or(LiquidityPoolError::Underflow)?,
+        );
+        storage::set_available(
+            &env,
+            available
+                .checked_sub(amount)
+                .ok_or(LiquidityPoolError::Underflow)?,
+        );
+
+        token::Client::new(&env, &storage::get_token(&env)).transfer(
+            &env.current_contract_address(),
+            &provider,
+            &amount,
+        );
+
+        events::emit_liquidity_withdrawn(&env, &provider, amount, shares);
+
+        Ok(amount)
+    }
+
+    /// Get the principal currently lent out to borrowers
+    pub fn get_total_loaned(env: Env) -> i128 {
+        storage::get_total_loaned(&env)
+    }
+
+    /// Get the total pool value (available liquidity plus outstanding principal)
+    pub fn get_total_pool_value(env: Env) -> Result<i128, LiquidityPoolError> {
+        Self::total_pool_value(&env)
+    }
+
+    /// Get the total number of shares issued
+    pub fn get_total_shares(env: Env) -> i128 {
+        storage::get_total_shares(&env)
+    }
+
+    /// Get a provider's share balance
+    pub fn get_shares(env: Env, provider: Address) -> i128 {
+        storage::read_shares(&env, &provider)
+    }
+
+    /// Get the value of one share, scaled by SHARE_VALUE_PRECISION
+    pub fn get_share_value(env: Env) -> Result<i128, LiquidityPoolError> {
+        Self::share_value(&env)
+    }
+
+    /// Set the CreditLine contract allowed to draw funds (admin only)
+    pub fn set_creditline(env: Env, admin: Address, address: Address) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        storage::set_creditline(&env, &address);
+    }
+
+    /// Start transferring the admin role to a new address (admin only)
+    /// The transfer only takes effect once the new address calls `accept_admin`
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_pending_admin(&env, &new_admin);
+        events::emit_admin_proposed(&env, &admin, &new_admin);
+    }
+
+    /// Complete a pending admin transfer, authorized by the proposed address
+    pub fn accept_admin(env: Env) -> Result<(), LiquidityPoolError> {
+        let new_admin =
+            storage::get_pending_admin(&env).ok_or(LiquidityPoolError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        let old_admin = storage::get_admin(&env);
+        storage::set_admin(&env, &new_admin);
+        storage::remove_pending_admin(&env);
+        events::emit_admin_changed(&env, &old_admin, &new_admin);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), LiquidityPoolError> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let pending_admin =
+            storage::get_pending_admin(&env).ok_or(LiquidityPoolError::NoPendingAdmin)?;
+        storage::remove_pending_admin(&env);
+        events::emit_admin_transfer_cancelled(&env, &admin, &pending_admin);
+        Ok(())
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        storage::get_admin(&env)
+    }
+
+    /// Get the address proposed as the next admin, if a transfer is pending
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Total pool value: available liquidity plus principal lent out
+    fn total_pool_value(env: &Env) -> Result<i128, LiquidityPoolError> {
+        storage::get_available(env)
+            .checked_add(storage::get_total_loaned(env))
+            .ok_or(LiquidityPoolError::Overflow)
+    }
+
+    /// Value of one share, 1:1 while no shares exist
+    fn share_value(env: &Env) -> Result<i128, LiquidityPoolError> {
+        let total_shares = storage::get_total_shares(env);
+        if total_shares == 0 {
+            return Ok(types::SHARE_VALUE_PRECISION);
+        }
+
+        Self::total_pool_value(env)?
+            .checked_mul(types::SHARE_VALUE_PRECISION)
+            .and_then(|v| v.checked_div(total_shares))
+            .ok_or(LiquidityPoolError::Overflow)
+    }
+}
+
+#[contractimpl]
+impl LiquidityPoolInterface for LiquidityPoolContract {
+    /// Pay a merchant for a new loan (CreditLine only)
+    fn fund_loan(
+        env: Env,
+        creditline: Address,
+        merchant: Address,
+        amount: i128,
+    ) -> Result<(), LiquidityPoolError> {
+        creditline.require_auth();
+        access::require_creditline(&env, &creditline);
+
+        if amount <= 0 {
+            return Err(LiquidityPoolError::InvalidAmount);
+        }
+
+        let available = storage::get_available(&env);
+        if amount > available {
+            return Err(LiquidityPoolError::InsufficientLiquidity);
+        }
+
+        storage::set_available(
+            &env,
+            available
+                .checked_sub(amount)
+                .ok_or(LiquidityPoolError::Underflow)?,
+        );
+        storage::set_total_loaned(
+            &env,
+            storage::get_total_loaned(&env)
+                .checked_add(amount)
+                .ok_or(LiquidityPoolError::Overflow)?,
+        );
+
+        token::Client::new(&env, &storage::get_token(&env)).transfer(
+            &env.current_contract_address(),
+            &merchant,
+            &amount,
+        );
+
+        events::emit_loan_funded(&env, &merchant, amount);
+
+        Ok(())
+    }
+
+    /// Record a repayment already transferred to the pool (CreditLine only)
+    /// Principal returns to available liquidity, interest raises the share value
+    fn receive_repayment(
+        env: Env,
+        creditline: Address,
+        principal: i128,
+        interest: i128,
+    ) -> Result<(), LiquidityPoolError> {
+        creditline.require_auth();
+        access::require_creditline(&env, &creditline);
+
+        if principal < 0 || interest < 0 {
+            return Err(LiquidityPoolError::InvalidAmount);
+        }
+
+        // More principal than is lent out means the books are off, refuse it rather than go negative
+        let total_loaned = storage::get_total_loaned(&env)
+            .checked_sub(principal)
+            .filter(|total_loaned| *total_loaned >= 0)
+            .ok_or(LiquidityPoolError::Underflow)?;
+
+        let received = principal
+            .checked_add(interest)
+            .ok_or(LiquidityPoolError::Overflow)?;
+
+        storage::set_total_loaned(&env, total_loaned);
+        storage::set_available(
+            &env,
+            storage::get_available(&env)
+                .checked_add(received)
+                .ok_or(LiquidityPoolError::Overflow)?,
+        );
+
+        events::emit_repayment_received(&env, principal, interest, Self::share_value(&env)?);
+
+        Ok(())
+    }
+
+    /// Write off the principal of a defaulted loan (CreditLine only)
+    /// `recovered` is the forfeited guarantee already transferred to the pool
+    fn record_default(
+        env: Env,
+        creditline: Address,
+        principal_lost: i128,
+        recovered: i128,
+    ) -> Result<(), LiquidityPoolError> {
+        creditline.require_auth();
+        access::require_creditline(&env, &creditline);
+
+        if principal_lost < 0 || recovered < 0 {
+            return Err(LiquidityPoolError::InvalidAmount);
+        }
+
+        // More principal than is lent out means the books are off, refuse it rather than go negative
+        let total_loaned = storage::get_total_loaned(&env)
+            .checked_sub(principal_lost)
+            .filter(|total_loaned| *total_loaned >= 0)
+            .ok_or(LiquidityPoolError::Underflow)?;
+
+        storage::set_total_loaned(&env, total_loaned);
+        storage::set_available(
+            &env,
+            storage::get_available(&env)
+                .checked_add(recovered)
+                .ok_or(LiquidityPoolError::Overflow)?,
+        );
+
+        events::emit_default_recorded(&env, principal_lost, recovered, Self::share_value(&env)?);
+
+        Ok(())
+    }
+
+    /// Get the liquidity that is not lent out
+    fn get_available_liquidity(env: Env) -> i128 {
+        storage::get_available(&env)
+    }
+}
+
+#[cfg(test)]
+mod tests;
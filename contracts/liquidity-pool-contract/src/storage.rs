@@ -0,0 +1,122 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::types::{DataKey, SHARES_TTL_EXTEND_TO, SHARES_TTL_THRESHOLD};
+
+// Storage keys
+pub const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+pub const PENDING_ADMIN_KEY: Symbol = symbol_short!("PENDADMIN");
+pub const TOKEN: Symbol = symbol_short!("TOKEN");
+pub const CREDITLINE: Symbol = symbol_short!("CREDLINE");
+pub const TOTAL_SHARES: Symbol = symbol_short!("TOTSHARES");
+pub const AVAILABLE: Symbol = symbol_short!("AVAILABLE");
+pub const TOTAL_LOANED: Symbol = symbol_short!("TOTLOANED");
+
+/// Get the admin address from storage
+pub fn get_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&ADMIN_KEY)
+        .unwrap_or_else(|| panic!("Admin not set"))
+}
+
+/// Set the admin address in storage
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&ADMIN_KEY, admin);
+}
+
+/// Get the address proposed as the next admin, if any
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&PENDING_ADMIN_KEY)
+}
+
+/// Set the address proposed as the next admin
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&PENDING_ADMIN_KEY, admin);
+}
+
+/// Clear the proposed admin
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().instance().remove(&PENDING_ADMIN_KEY);
+}
+
+/// Get the pool token address
+pub fn get_token(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&TOKEN)
+        .unwrap_or_else(|| panic!("Token not set"))
+}
+
+/// Set the pool token address
+pub fn set_token(env: &Env, token: &Address) {
+    env.storage().instance().set(&TOKEN, token);
+}
+
+/// Get the CreditLine Contract address
+pub fn get_creditline(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&CREDITLINE)
+}
+
+/// Set the CreditLine Contract address
+pub fn set_creditline(env: &Env, address: &Address) {
+    env.storage().instance().set(&CREDITLINE, address);
+}
+
+/// Get the total number of shares issued
+pub fn get_total_shares(env: &Env) -> i128 {
+    env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0)
+}
+
+/// Set the total number of shares issued
+pub fn set_total_shares(env: &Env, shares: i128) {
+    env.storage().instance().set(&TOTAL_SHARES, &shares);
+}
+
+/// Get the liquidity available for new loans and withdrawals
+pub fn get_available(env: &Env) -> i128 {
+    env.storage().instance().get(&AVAILABLE).unwrap_or(0)
+}
+
+/// Set the liquidity available for new loans and withdrawals
+pub fn set_available(env: &Env, amount: i128) {
+    env.storage().instance().set(&AVAILABLE, &amount);
+}
+
+/// Get the principal currently lent out to borrowers
+pub fn get_total_loaned(env: &Env) -> i128 {
+    env.storage().instance().get(&TOTAL_LOANED).unwrap_or(0)
+}
+
+/// Set the principal currently lent out to borrowers
+pub fn set_total_loaned(env: &Env, amount: i128) {
+    env.storage().instance().set(&TOTAL_LOANED, &amount);
+}
+
+/// Read a provider's share balance, extending its TTL when present
+pub fn read_shares(env: &Env, provider: &Address) -> i128 {
+    let key = DataKey::Shares(provider.clone());
+    let shares: Option<i128> = env.storage().persistent().get(&key);
+
+    if shares.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, SHARES_TTL_THRESHOLD, SHARES_TTL_EXTEND_TO);
+    }
+
+    shares.unwrap_or(0)
+}
+
+/// Write a provider's share balance, removing the entry when it reaches zero
+pub fn write_shares(env: &Env, provider: &Address, shares: i128) {
+    let key = DataKey::Shares(provider.clone());
+
+    if shares == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+
+    env.storage().persistent().set(&key, &shares);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, SHARES_TTL_THRESHOLD, SHARES_TTL_EXTEND_TO);
+}
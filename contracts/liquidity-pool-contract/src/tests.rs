@@ -0,0 +1,491 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{LiquidityPoolContract, LiquidityPoolContractClient, LiquidityPoolError};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    token, vec, Address, BytesN, Env, IntoVal, Symbol,
+};
+
+// Tests register the contract natively, so any hash stands in for the deployed code
+fn deployed_wasm_hash(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[1; 32])
+}
+
+struct PoolTest<'a> {
+    client: LiquidityPoolContractClient<'a>,
+    contract_id: Address,
+    admin: Address,
+    creditline: Address,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+}
+
+fn setup(env: &Env) -> PoolTest<'_> {
+    let contract_id = env.register(LiquidityPoolContract, ());
+    let client = LiquidityPoolContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let creditline = Address::generate(env);
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = token::Client::new(env, &sac.address());
+    let token_admin = token::StellarAssetClient::new(env, &sac.address());
+
+    client.initialize(
+        &admin,
+        &sac.address(),
+        &creditline,
+        &deployed_wasm_hash(env),
+    );
+
+    PoolTest {
+        client,
+        contract_id,
+        admin,
+        creditline,
+        token,
+        token_admin,
+    }
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    assert_eq!(t.client.get_admin(), t.admin);
+    assert_eq!(t.client.get_available_liquidity(), 0);
+    assert_eq!(t.client.get_total_shares(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    t.client.initialize(
+        &t.admin,
+        &t.token.address,
+        &t.creditline,
+        &deployed_wasm_hash(&env),
+    );
+}
+
+#[test]
+fn test_get_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    assert_eq!(t.client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(t.client.get_wasm_hash(), Some(deployed_wasm_hash(&env)));
+}
+
+// Smallest WASM the host accepts: no code, only the env meta section for protocol 22
+const EMPTY_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x00, 0x1e, 0x11, b'c', b'o', b'n', b't', b'r',
+    b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0', 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn test_upgrade_replaces_code() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let new_wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(&env, EMPTY_WASM));
+
+    t.client.upgrade(&t.admin, &new_wasm_hash);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("UPGRADED"),).into_val(&env),
+                (Some(deployed_wasm_hash(&env)), new_wasm_hash.clone()).into_val(&env),
+            )
+        ]
+    );
+    let stored_hash = env.as_contract(&t.contract_id, || common::upgrade::get_wasm_hash(&env));
+    assert_eq!(stored_hash, Some(new_wasm_hash));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")] // NotAdmin
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    t.client.upgrade(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+}
+
+#[test]
+fn test_migrate_runs_once_per_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    assert_eq!(
+        t.client.try_migrate(),
+        Err(Ok(LiquidityPoolError::AlreadyMigrated))
+    );
+
+    // Deployed before versions were tracked
+    env.as_contract(&t.contract_id, || {
+        env.storage()
+            .instance()
+            .remove(&common::upgrade::VERSION_KEY);
+    });
+    assert_eq!(t.client.get_version(), 0);
+
+    assert_eq!(t.client.migrate(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                t.contract_id.clone(),
+                (symbol_short!("MIGRATED"),).into_val(&env),
+                (0u32, crate::types::CONTRACT_VERSION).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(t.client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        t.client.try_migrate(),
+        Err(Ok(LiquidityPoolError::AlreadyMigrated))
+    );
+}
+
+#[test]
+fn test_first_deposit_issues_shares_one_to_one() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &10_000);
+
+    let shares = t.client.deposit(&provider, &10_000);
+
+    assert_eq!(shares, 10_000);
+    assert_eq!(t.client.get_shares(&provider), 10_000);
+    assert_eq!(t.client.get_total_shares(), 10_000);
+    assert_eq!(t.client.get_available_liquidity(), 10_000);
+    assert_eq!(t.token.balance(&t.contract_id), 10_000);
+    assert_eq!(t.token.balance(&provider), 0);
+}
+
+#[test]
+fn test_deposit_after_interest_issues_fewer_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    t.token_admin.mint(&first, &1_000);
+    t.token_admin.mint(&second, &1_100);
+
+    t.client.deposit(&first, &1_000);
+
+    // Lend 500 and get it back with 100 interest: pool value 1_100 for 1_000 shares
+    t.client.fund_loan(&t.creditline, &merchant, &500);
+    t.token_admin.mint(&t.contract_id, &600);
+    t.client.receive_repayment(&t.creditline, &500, &100);
+
+    // shares = 1_100 * 1_000 / 1_100
+    let shares = t.client.deposit(&second, &1_100);
+    assert_eq!(shares, 1_000);
+    assert_eq!(t.client.get_total_shares(), 2_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // InvalidAmount
+fn test_deposit_zero_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    t.client.deposit(&Address::generate(&env), &0);
+}
+
+#[test]
+fn test_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    let amount = t.client.withdraw(&provider, &400);
+
+    assert_eq!(amount, 400);
+    assert_eq!(t.client.get_shares(&provider), 600);
+    assert_eq!(t.client.get_total_shares(), 600);
+    assert_eq!(t.client.get_available_liquidity(), 600);
+    assert_eq!(t.token.balance(&provider), 400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")] // InsufficientShares
+fn test_withdraw_more_shares_than_owned_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client.withdraw(&provider, &1_001);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")] // InsufficientLiquidity
+fn test_withdraw_lent_out_liquidity_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client
+        .fund_loan(&t.creditline, &Address::generate(&env), &800);
+
+    // Shares are worth 1_000 but only 200 is available
+    t.client.withdraw(&provider, &1_000);
+}
+
+#[test]
+fn test_fund_loan_pays_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client.fund_loan(&t.creditline, &merchant, &800);
+
+    assert_eq!(t.token.balance(&merchant), 800);
+    assert_eq!(t.client.get_available_liquidity(), 200);
+    assert_eq!(t.client.get_total_loaned(), 800);
+    assert_eq!(t.client.get_total_pool_value(), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")] // NotCreditLine
+fn test_fund_loan_not_creditline_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client.fund_loan(&stranger, &stranger, &800);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")] // InsufficientLiquidity
+fn test_fund_loan_insufficient_liquidity_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client
+        .fund_loan(&t.creditline, &Address::generate(&env), &1_001);
+}
+
+#[test]
+fn test_receive_repayment_raises_share_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    assert_eq!(t.client.get_share_value(), 10_000_000);
+
+    t.client
+        .fund_loan(&t.creditline, &Address::generate(&env), &800);
+    t.token_admin.mint(&t.contract_id, &880);
+    t.client.receive_repayment(&t.creditline, &800, &80);
+
+    assert_eq!(t.client.get_total_loaned(), 0);
+    assert_eq!(t.client.get_available_liquidity(), 1_080);
+    assert_eq!(t.client.get_share_value(), 10_800_000);
+
+    // The provider can now withdraw principal plus interest
+    assert_eq!(t.client.withdraw(&provider, &1_000), 1_080);
+    assert_eq!(t.token.balance(&provider), 1_080);
+}
+
+#[test]
+fn test_record_default_writes_off_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client
+        .fund_loan(&t.creditline, &Address::generate(&env), &800);
+
+    // 800 lost, 200 guarantee recovered
+    t.token_admin.mint(&t.contract_id, &200);
+    t.client.record_default(&t.creditline, &800, &200);
+
+    assert_eq!(t.client.get_total_loaned(), 0);
+    assert_eq!(t.client.get_available_liquidity(), 400);
+    assert_eq!(t.client.get_share_value(), 4_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")] // Underflow
+fn test_receive_repayment_more_than_loaned_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    t.client.receive_repayment(&t.creditline, &1, &0);
+}
+
+#[test]
+fn test_unbacked_principal_returns_underflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client
+        .fund_loan(&t.creditline, &Address::generate(&env), &300);
+
+    // Repaying or writing off more than is lent out is an error, not a negative balance
+    assert_eq!(
+        t.client.try_receive_repayment(&t.creditline, &301, &0),
+        Err(Ok(LiquidityPoolError::Underflow))
+    );
+    assert_eq!(
+        t.client.try_record_default(&t.creditline, &301, &0),
+        Err(Ok(LiquidityPoolError::Underflow))
+    );
+    assert_eq!(t.client.get_total_loaned(), 300);
+    assert_eq!(t.client.get_available_liquidity(), 700);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    // Proposing does not hand over the role yet
+    t.client.propose_admin(&new_admin);
+    assert_eq!(t.client.get_admin(), t.admin);
+    assert_eq!(t.client.get_pending_admin(), Some(new_admin.clone()));
+
+    // The new admin must authorize the acceptance
+    t.client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    t.contract_id.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+
+    assert_eq!(t.client.get_admin(), new_admin);
+    assert_eq!(t.client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    t.client.propose_admin(&Address::generate(&env));
+    t.client.cancel_admin_transfer();
+
+    assert_eq!(t.client.get_admin(), t.admin);
+    assert_eq!(t.client.get_pending_admin(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")] // NoPendingAdmin
+fn test_accept_admin_without_proposal_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+
+    t.client.accept_admin();
+}
+
+#[test]
+fn test_set_creditline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let t = setup(&env);
+    let provider = Address::generate(&env);
+    let new_creditline = Address::generate(&env);
+    t.token_admin.mint(&provider, &1_000);
+
+    t.client.deposit(&provider, &1_000);
+    t.client.set_creditline(&t.admin, &new_creditline);
+
+    t.client
+        .fund_loan(&new_creditline, &Address::generate(&env), &100);
+    assert_eq!(t.client.get_total_loaned(), 100);
+}
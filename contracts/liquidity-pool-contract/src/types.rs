@@ -0,0 +1,17 @@
+use soroban_sdk::{contracttype, Address};
+
+// Storage keys for per-provider entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Shares(Address),
+}
+
+// Constants
+pub const CONTRACT_VERSION: u32 = 1; // Storage layout version of this code, see `migrate`
+pub const SHARE_VALUE_PRECISION: i128 = 10_000_000; // Share value is reported with 7 decimals
+
+// Persistent entry TTL (in ledgers)
+pub const DAY_IN_LEDGERS: u32 = 17280;
+pub const SHARES_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const SHARES_TTL_EXTEND_TO: u32 = 90 * DAY_IN_LEDGERS;
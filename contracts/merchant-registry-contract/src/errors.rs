@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+// Error types for the merchant registry contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MerchantRegistryError {
+    NotAdmin = 1,
+    MerchantAlreadyRegistered = 2,
+    MerchantNotFound = 3,
+    InvalidMerchantName = 4,
+    NoPendingAdmin = 5,
+    AlreadyMigrated = 6,
+}
@@ -0,0 +1,67 @@
+use soroban_sdk::{symbol_short, Address, Env, String, Symbol};
+
+// Event topics
+const MERCHANT_REGISTERED: Symbol = symbol_short!("MRCHREGD");
+const MERCHANT_UPDATED: Symbol = symbol_short!("MRCHUPD");
+const MERCHANT_STATUS: Symbol = symbol_short!("MRCHSTAT");
+const ADMIN_PROPOSED: Symbol = symbol_short!("ADMINPROP");
+const ADMIN_CHANGED: Symbol = symbol_short!("ADMINCHGD");
+const ADMIN_TRANSFER_CANCELLED: Symbol = symbol_short!("ADMINCNCL");
+
+/// Emit a merchant registered event
+pub fn emit_merchant_registered(env: &Env, merchant: &Address, name: &String, category: &Symbol) {
+    env.events().publish(
+        (MERCHANT_REGISTERED, merchant),
+        (name.clone(), category.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit a merchant metadata updated event
+pub fn emit_merchant_updated(
+    env: &Env,
+    merchant: &Address,
+    name: &String,
+    category: &Symbol,
+    payout_address: &Address,
+) {
+    env.events().publish(
+        (MERCHANT_UPDATED, merchant),
+        (name.clone(), category.clone(), payout_address.clone()),
+    );
+}
+
+/// Emit a merchant activated/deactivated event
+pub fn emit_merchant_status_changed(env: &Env, merchant: &Address, active: bool) {
+    env.events().publish(
+        (MERCHANT_STATUS, merchant),
+        (active, env.ledger().timestamp()),
+    );
+}
+
+/// Emit an admin proposed event when a transfer starts
+pub fn emit_admin_proposed(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_PROPOSED, admin),
+        (pending_admin.clone(), env.ledger().timestamp()),
+    );
+}
+
+/// Emit an admin changed event when the proposed admin accepts
+pub fn emit_admin_changed(env: &Env, old_admin: &Address, new_admin: &Address) {
+    env.events().publish(
+        (ADMIN_CHANGED,),
+        (
+            old_admin.clone(),
+            new_admin.clone(),
+            env.ledger().timestamp(),
+        ),
+    );
+}
+
+/// Emit an admin transfer cancelled event
+pub fn emit_admin_transfer_cancelled(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_TRANSFER_CANCELLED, admin),
+        (pending_admin.clone(), env.ledger().timestamp()),
+    );
+}
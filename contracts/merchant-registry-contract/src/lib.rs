@@ -0,0 +1,261 @@
+#![no_std]
+use common::upgrade;
+use interfaces::MerchantRegistryInterface;
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String, Symbol};
+
+// Module imports
+mod access;
+mod errors;
+mod events;
+mod storage;
+mod types;
+
+// Re-export types for external use
+pub use errors::MerchantRegistryError;
+pub use types::MerchantInfo;
+
+/// Merchant Registry contract structure
+#[contract]
+pub struct MerchantRegistryContract;
+
+/// Contract implementation
+#[contractimpl]
+impl MerchantRegistryContract {
+    /// Get the storage version the contract was last migrated to
+    /// Set to this code's version at initialize and bumped by `migrate` after each upgrade
+    pub fn get_version(env: Env) -> u32 {
+        upgrade::get_version(&env)
+    }
+
+    /// Initialize the contract with its admin and the deployed code hash
+    /// Can only be called once (when admin is not set)
+    pub fn initialize(env: Env, admin: Address, wasm_hash: BytesN<32>) {
+        let admin_opt: Option<Address> = env.storage().instance().get(&storage::ADMIN_KEY);
+        if admin_opt.is_some() {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+
+        storage::set_admin(&env, &admin);
+        upgrade::set_version(&env, types::CONTRACT_VERSION);
+        upgrade::set_wasm_hash(&env, &wasm_hash);
+    }
+
+    /// Install new contract code from an uploaded WASM hash (admin only)
+    /// The new code runs from the next call, which should be `migrate`
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        upgrade::upgrade(&env, &new_wasm_hash);
+    }
+
+    /// Bring storage from the stored version up to this code's version
+    /// Anyone can call this after an upgrade, each version bump migrates once.
+    /// Returns the new storage version
+    pub fn migrate(env: Env) -> Result<u32, MerchantRegistryError> {
+        let from_version = upgrade::get_version(&env);
+        if from_version >= types::CONTRACT_VERSION {
+            return Err(MerchantRegistryError::AlreadyMigrated);
+        }
+
+        // Layout changes are applied here, one step per version. Deployments from
+        // before versions were tracked (version 0) share the version 1 layout
+
+        upgrade::finish_migration(&env, from_version, types::CONTRACT_VERSION);
+        Ok(types::CONTRACT_VERSION)
+    }
+
+    /// Get the hash of the installed code, recorded at initialize and on every upgrade
+    pub fn get_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        upgrade::get_wasm_hash(&env)
+    }
+
+    /// Register a new merchant (admin only)
+    /// Merchants are active as soon as they are registered
+    pub fn register_merchant(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+        name: String,
+        category: Symbol,
+        payout_address: Address,
+    ) -> Result<(), MerchantRegistryError> {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        if storage::read_merchant(&env, &merchant).is_some() {
+            return Err(MerchantRegistryError::MerchantAlreadyRegistered);
+        }
+
+        Self::validate_name(&name)?;
+
+        let info = MerchantInfo {
+            address: merchant.clone(),
+            name: name.clone(),
+            category: category.clone(),
+            payout_address,
+            active: true,
+            registered_at: env.ledger().timestamp(),
+        };
+
+        storage::write_merchant(&env, &info);
+
+        events::emit_merchant_registered(&env, &merchant, &name, &category);
+
+        Ok(())
+    }
+
+    /// Update a merchant's metadata (admin only)
+    pub fn update_merchant(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+        name: String,
+        category: Symbol,
+        payout_address: Address,
+    ) -> Result<(), MerchantRegistryError> {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        let mut info = storage::read_merchant(&env, &merchant)
+            .ok_or(MerchantRegistryError::MerchantNotFound)?;
+
+        Self::validate_name(&name)?;
+
+        info.name = name;
+        info.category = category;
+        info.payout_address = payout_address;
+
+        storage::write_merchant(&env, &info);
+
+        events::emit_merchant_updated(
+            &env,
+            &merchant,
+            &info.name,
+            &info.category,
+            &info.payout_address,
+        );
+
+        Ok(())
+    }
+
+    /// Reactivate a previously deactivated merchant (admin only)
+    pub fn activate_merchant(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+    ) -> Result<(), MerchantRegistryError> {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        Self::set_active(&env, &merchant, true)
+    }
+
+    /// Suspend a merchant so it can no longer receive loans (admin only)
+    pub fn deactivate_merchant(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+    ) -> Result<(), MerchantRegistryError> {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        Self::set_active(&env, &merchant, false)
+    }
+
+    /// Get a merchant's details
+    pub fn get_merchant(env: Env, merchant: Address) -> MerchantInfo {
+        storage::read_merchant(&env, &merchant)
+            .unwrap_or_else(|| panic_with_error!(&env, MerchantRegistryError::MerchantNotFound))
+    }
+
+    /// Start transferring the admin role to a new address (admin only)
+    /// The transfer only takes effect once the new address calls `accept_admin`
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_pending_admin(&env, &new_admin);
+        events::emit_admin_proposed(&env, &admin, &new_admin);
+    }
+
+    /// Complete a pending admin transfer, authorized by the proposed address
+    pub fn accept_admin(env: Env) -> Result<(), MerchantRegistryError> {
+        let new_admin =
+            storage::get_pending_admin(&env).ok_or(MerchantRegistryError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        let old_admin = storage::get_admin(&env);
+        storage::set_admin(&env, &new_admin);
+        storage::remove_pending_admin(&env);
+        events::emit_admin_changed(&env, &old_admin, &new_admin);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), MerchantRegistryError> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let pending_admin =
+            storage::get_pending_admin(&env).ok_or(MerchantRegistryError::NoPendingAdmin)?;
+        storage::remove_pending_admin(&env);
+        events::emit_admin_transfer_cancelled(&env, &admin, &pending_admin);
+        Ok(())
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        storage::get_admin(&env)
+    }
+
+    /// Get the address proposed as the next admin, if a transfer is pending
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Validate merchant name is between 1 and 64 characters
+    fn validate_name(name: &String) -> Result<(), MerchantRegistryError> {
+        if name.is_empty() || name.len() > types::MAX_NAME_LENGTH {
+            return Err(MerchantRegistryError::InvalidMerchantName);
+        }
+
+        Ok(())
+    }
+
+    /// Update a merchant's active flag and emit the status change
+    fn set_active(
+        env: &Env,
+        merchant: &Address,
+        active: bool,
+    ) -> Result<(), MerchantRegistryError> {
+        let mut info =
+            storage::read_merchant(env, merchant).ok_or(MerchantRegistryError::MerchantNotFound)?;
+
+        info.active = active;
+        storage::write_merchant(env, &info);
+
+        events::emit_merchant_status_changed(env, merchant, active);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MerchantRegistryInterface for MerchantRegistryContract {
+    /// Check if a merchant is registered and active
+    fn is_active(env: Env, merchant: Address) -> bool {
+        storage::read_merchant(&env, &merchant)
+            .map(|info| info.active)
+            .unwrap_or(false)
+    }
+
+    /// Get the address a merchant's loans are paid out to
+    fn get_payout_address(env: Env, merchant: Address) -> Address {
+        Self::get_merchant(env, merchant).payout_address
+    }
+}
+
+#[cfg(test)]
+mod tests;
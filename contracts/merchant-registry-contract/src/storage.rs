@@ -0,0 +1,58 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::types::{DataKey, MerchantInfo, MERCHANT_TTL_EXTEND_TO, MERCHANT_TTL_THRESHOLD};
+
+// Storage keys
+pub const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+pub const PENDING_ADMIN_KEY: Symbol = symbol_short!("PENDADMIN");
+
+/// Get the admin address from storage
+pub fn get_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&ADMIN_KEY)
+        .unwrap_or_else(|| panic!("Admin not set"))
+}
+
+/// Set the admin address in storage
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&ADMIN_KEY, admin);
+}
+
+/// Get the address proposed as the next admin, if any
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&PENDING_ADMIN_KEY)
+}
+
+/// Set the address proposed as the next admin
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&PENDING_ADMIN_KEY, admin);
+}
+
+/// Clear the proposed admin
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().instance().remove(&PENDING_ADMIN_KEY);
+}
+
+/// Read a merchant from storage, extending its TTL when found
+pub fn read_merchant(env: &Env, merchant: &Address) -> Option<MerchantInfo> {
+    let key = DataKey::Merchant(merchant.clone());
+    let info: Option<MerchantInfo> = env.storage().persistent().get(&key);
+
+    if info.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, MERCHANT_TTL_THRESHOLD, MERCHANT_TTL_EXTEND_TO);
+    }
+
+    info
+}
+
+/// Write a merchant to storage
+pub fn write_merchant(env: &Env, info: &MerchantInfo) {
+    let key = DataKey::Merchant(info.address.clone());
+    env.storage().persistent().set(&key, info);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, MERCHANT_TTL_THRESHOLD, MERCHANT_TTL_EXTEND_TO);
+}
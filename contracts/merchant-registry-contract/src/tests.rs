@@ -0,0 +1,400 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{MerchantRegistryContract, MerchantRegistryContractClient, MerchantRegistryError};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol,
+};
+
+// Tests register the contract natively, so any hash stands in for the deployed code
+fn deployed_wasm_hash(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[1; 32])
+}
+
+fn setup(env: &Env) -> (MerchantRegistryContractClient<'_>, Address) {
+    let contract_id = env.register(MerchantRegistryContract, ());
+    let client = MerchantRegistryContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin, &deployed_wasm_hash(env));
+
+    (client, admin)
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    client.initialize(&admin, &deployed_wasm_hash(&env));
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let new_admin = Address::generate(&env);
+
+    // Proposing does not hand over the role yet
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    // The new admin must authorize the acceptance
+    client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    client.propose_admin(&Address::generate(&env));
+    client.cancel_admin_transfer();
+
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")] // NoPendingAdmin
+fn test_accept_admin_without_proposal_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+
+    client.accept_admin();
+}
+
+#[test]
+fn test_get_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    assert_eq!(client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(client.get_wasm_hash(), Some(deployed_wasm_hash(&env)));
+}
+
+// Smallest WASM the host accepts: no code, only the env meta section for protocol 22
+const EMPTY_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x00, 0x1e, 0x11, b'c', b'o', b'n', b't', b'r',
+    b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0', 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn test_upgrade_replaces_code() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+    let new_wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(&env, EMPTY_WASM));
+
+    client.upgrade(&admin, &new_wasm_hash);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("UPGRADED"),).into_val(&env),
+                (Some(deployed_wasm_hash(&env)), new_wasm_hash.clone()).into_val(&env),
+            )
+        ]
+    );
+    let stored_hash = env.as_contract(&client.address, || common::upgrade::get_wasm_hash(&env));
+    assert_eq!(stored_hash, Some(new_wasm_hash));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")] // NotAdmin
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+
+    client.upgrade(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+}
+
+#[test]
+fn test_migrate_runs_once_per_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+    assert_eq!(
+        client.try_migrate(),
+        Err(Ok(MerchantRegistryError::AlreadyMigrated))
+    );
+
+    // Deployed before versions were tracked
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .remove(&common::upgrade::VERSION_KEY);
+    });
+    assert_eq!(client.get_version(), 0);
+
+    assert_eq!(client.migrate(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("MIGRATED"),).into_val(&env),
+                (0u32, crate::types::CONTRACT_VERSION).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_version(), crate::types::CONTRACT_VERSION);
+    assert_eq!(
+        client.try_migrate(),
+        Err(Ok(MerchantRegistryError::AlreadyMigrated))
+    );
+}
+
+#[test]
+fn test_register_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    let merchant = Address::generate(&env);
+    let payout = Address::generate(&env);
+    let name = String::from_str(&env, "Laptop Store");
+
+    client.register_merchant(&admin, &merchant, &name, &symbol_short!("retail"), &payout);
+
+    let info = client.get_merchant(&merchant);
+    assert_eq!(info.address, merchant);
+    assert_eq!(info.name, name);
+    assert_eq!(info.category, symbol_short!("retail"));
+    assert_eq!(info.payout_address, payout);
+    assert!(info.active);
+    assert!(client.is_active(&merchant));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")] // NotAdmin
+fn test_register_merchant_not_admin_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+
+    let stranger = Address::generate(&env);
+    let merchant = Address::generate(&env);
+
+    client.register_merchant(
+        &stranger,
+        &merchant,
+        &String::from_str(&env, "Shop"),
+        &symbol_short!("retail"),
+        &merchant,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")] // MerchantAlreadyRegistered
+fn test_register_merchant_twice_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    let merchant = Address::generate(&env);
+    let name = String::from_str(&env, "Shop");
+
+    client.register_merchant(
+        &admin,
+        &merchant,
+        &name,
+        &symbol_short!("retail"),
+        &merchant,
+    );
+    client.register_merchant(
+        &admin,
+        &merchant,
+        &name,
+        &symbol_short!("retail"),
+        &merchant,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")] // InvalidMerchantName
+fn test_register_merchant_empty_name_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    let merchant = Address::generate(&env);
+
+    client.register_merchant(
+        &admin,
+        &merchant,
+        &String::from_str(&env, ""),
+        &symbol_short!("retail"),
+        &merchant,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")] // InvalidMerchantName
+fn test_register_merchant_name_too_long_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    let merchant = Address::generate(&env);
+    let long_name = String::from_str(
+        &env,
+        "This merchant name is definitely much longer than sixty-four chars",
+    );
+
+    client.register_merchant(
+        &admin,
+        &merchant,
+        &long_name,
+        &symbol_short!("retail"),
+        &merchant,
+    );
+}
+
+#[test]
+fn test_deactivate_and_activate_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(
+        &admin,
+        &merchant,
+        &String::from_str(&env, "Shop"),
+        &symbol_short!("retail"),
+        &merchant,
+    );
+
+    client.deactivate_merchant(&admin, &merchant);
+    assert!(!client.is_active(&merchant));
+    assert!(!client.get_merchant(&merchant).active);
+
+    client.activate_merchant(&admin, &merchant);
+    assert!(client.is_active(&merchant));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // MerchantNotFound
+fn test_deactivate_unknown_merchant_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    client.deactivate_merchant(&admin, &Address::generate(&env));
+}
+
+#[test]
+fn test_update_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup(&env);
+
+    let merchant = Address::generate(&env);
+    client.register_merchant(
+        &admin,
+        &merchant,
+        &String::from_str(&env, "Shop"),
+        &symbol_short!("retail"),
+        &merchant,
+    );
+
+    let new_payout = Address::generate(&env);
+    let new_name = String::from_str(&env, "Shop & Co");
+    client.update_merchant(
+        &admin,
+        &merchant,
+        &new_name,
+        &symbol_short!("travel"),
+        &new_payout,
+    );
+
+    let info = client.get_merchant(&merchant);
+    assert_eq!(info.name, new_name);
+    assert_eq!(info.category, symbol_short!("travel"));
+    assert_eq!(info.payout_address, new_payout);
+    assert!(info.active);
+    assert_eq!(client.get_payout_address(&merchant), new_payout);
+}
+
+#[test]
+fn test_is_active_unregistered_merchant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+
+    assert!(!client.is_active(&Address::generate(&env)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // MerchantNotFound
+fn test_get_unknown_merchant_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup(&env);
+
+    client.get_merchant(&Address::generate(&env));
+}
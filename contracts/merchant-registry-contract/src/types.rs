@@ -0,0 +1,29 @@
+use soroban_sdk::{contracttype, Address, String, Symbol};
+
+// Storage keys for per-merchant entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Merchant(Address),
+}
+
+// Merchant data structure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantInfo {
+    pub address: Address,
+    pub name: String,
+    pub category: Symbol,
+    pub payout_address: Address, // Where loan proceeds are sent
+    pub active: bool,
+    pub registered_at: u64, // Unix timestamp
+}
+
+// Constants
+pub const CONTRACT_VERSION: u32 = 1; // Storage layout version of this code, see `migrate`
+pub const MAX_NAME_LENGTH: u32 = 64;
+
+// Persistent entry TTL (in ledgers)
+pub const DAY_IN_LEDGERS: u32 = 17280;
+pub const MERCHANT_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const MERCHANT_TTL_EXTEND_TO: u32 = 90 * DAY_IN_LEDGERS;
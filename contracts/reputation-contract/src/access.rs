@@ -1,3 +1,4 @@
+use common::{governance, roles, Role};
 use soroban_sdk::{panic_with_error, Address, Env};
 
 use crate::errors::ReputationError;
@@ -5,13 +6,19 @@ use crate::storage;
 
 /// Require that the given address is the admin, otherwise panic with NotAdmin error
 pub fn require_admin(env: &Env, caller: &Address) {
-    let admin = storage::get_admin(env);
-    
-    if caller != &admin {
+    if !roles::is_admin(env, caller) {
         panic_with_error!(env, ReputationError::NotAdmin);
     }
 }
 
+/// Require that the given address holds a role, otherwise panic with MissingRole error
+/// The admin holds every role
+pub fn require_role(env: &Env, role: Role, caller: &Address) {
+    if !roles::has_role(env, role, caller) {
+        panic_with_error!(env, ReputationError::MissingRole);
+    }
+}
+
 /// Require that the given address is an authorized updater, otherwise panic with NotUpdater error
 pub fn require_updater(env: &Env, addr: &Address) {
     if !storage::is_updater(env, addr) {
@@ -19,3 +26,24 @@ pub fn require_updater(env: &Env, addr: &Address) {
     }
 }
 
+
+/// Require that the given address is in the signer set, otherwise panic with NotSigner error
+pub fn require_signer(env: &Env, addr: &Address) {
+    if !governance::is_signer(env, addr) {
+        panic_with_error!(env, ReputationError::NotSigner);
+    }
+}
+
+/// Require that none of the given pause flags are set, otherwise panic with ContractPaused error
+pub fn require_not_paused(env: &Env, flags: u32) {
+    if storage::get_paused(env) & flags != 0 {
+        panic_with_error!(env, ReputationError::ContractPaused);
+    }
+}
+
+/// Require that sensitive actions aren't under signer governance, otherwise panic with GovernanceRequired error
+pub fn require_no_governance(env: &Env) {
+    if governance::is_enabled(env) {
+        panic_with_error!(env, ReputationError::GovernanceRequired);
+    }
+}
@@ -10,5 +10,16 @@ pub enum ReputationError {
     OutOfBounds = 3,
     Overflow = 4,
     Underflow = 5,
+    ContractPaused = 6,
+    AlreadyInitialized = 7,
+    NoPendingAdmin = 8,
+    MissingRole = 9,
+    NotSigner = 10,
+    ProposalNotFound = 11,
+    ProposalExecuted = 12,
+    AlreadyApproved = 13,
+    InvalidSignerSet = 14,
+    GovernanceRequired = 15,
+    AlreadyMigrated = 16,
 }
 
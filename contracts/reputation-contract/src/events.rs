@@ -4,6 +4,9 @@ use soroban_sdk::{symbol_short, Address, Env, Symbol};
 const SCORE_CHANGED: Symbol = symbol_short!("SCORECHGD");
 const UPDATER_CHANGED: Symbol = symbol_short!("UPDCHGD");
 const ADMIN_CHANGED: Symbol = symbol_short!("ADMINCHGD");
+const PAUSE_CHANGED: Symbol = symbol_short!("PAUSECHG");
+const ADMIN_PROPOSED: Symbol = symbol_short!("ADMINPROP");
+const ADMIN_TRANSFER_CANCELLED: Symbol = symbol_short!("ADMINCNCL");
 
 /// Emit a score changed event
 pub fn emit_score_changed(
@@ -35,3 +38,26 @@ pub fn emit_admin_changed(env: &Env, old_admin: &Address, new_admin: &Address) {
     );
 }
 
+/// Emit an admin proposed event
+pub fn emit_admin_proposed(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_PROPOSED, admin),
+        pending_admin.clone(),
+    );
+}
+
+/// Emit an admin transfer cancelled event
+pub fn emit_admin_transfer_cancelled(env: &Env, admin: &Address, pending_admin: &Address) {
+    env.events().publish(
+        (ADMIN_TRANSFER_CANCELLED, admin),
+        pending_admin.clone(),
+    );
+}
+
+/// Emit a pause changed event with the previous and new pause flags
+pub fn emit_pause_changed(env: &Env, caller: &Address, old_flags: u32, new_flags: u32) {
+    env.events().publish(
+        (PAUSE_CHANGED, caller),
+        (old_flags, new_flags),
+    );
+}
@@ -1,5 +1,7 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+use common::{governance, roles, upgrade};
+use interfaces::ReputationInterface;
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, Map, Symbol, Vec};
 
 // Module imports
 mod types;
@@ -9,7 +11,11 @@ mod events;
 mod errors;
 
 // Re-export types for external use
+pub use common::{GovAction, Proposal, Role};
 pub use errors::ReputationError;
+pub use types::{
+    PenaltyParams, RewardParams, PAUSE_ALL, PAUSE_PENALTIES, PAUSE_REWARDS, PAUSE_SET_SCORE,
+};
 
 /// Reputation contract structure
 #[contract]
@@ -18,14 +24,10 @@ pub struct ReputationContract;
 /// Contract implementation
 #[contractimpl]
 impl ReputationContract {
-    /// Get the version of this contract
-    pub fn get_version() -> Symbol {
-        symbol_short!("v1_0_0")
-    }
-
-    /// Get the reputation score for a user
-    pub fn get_score(env: Env, user: Address) -> u32 {
-        storage::read_score(&env, &user)
+    /// Get the storage version the contract was last migrated to
+    /// Set to this code's version at initialize and bumped by `migrate` after each upgrade
+    pub fn get_version(env: Env) -> u32 {
+        upgrade::get_version(&env)
     }
 
     /// Increase a user's reputation score by a given amount
@@ -33,11 +35,12 @@ impl ReputationContract {
     pub fn increase_score(env: Env, updater: Address, user: Address, amount: u32) {
         updater.require_auth();
         access::require_updater(&env, &updater);
+        access::require_not_paused(&env, types::PAUSE_REWARDS);
 
         let old_score = storage::read_score(&env, &user);
         let new_score = old_score
             .checked_add(amount)
-            .ok_or_else(|| ReputationError::Overflow)
+            .ok_or(ReputationError::Overflow)
             .unwrap();
 
         if new_score > types::MAX_SCORE {
@@ -55,14 +58,15 @@ impl ReputationContract {
     pub fn decrease_score(env: Env, updater: Address, user: Address, amount: u32) {
         updater.require_auth();
         access::require_updater(&env, &updater);
+        access::require_not_paused(&env, types::PAUSE_PENALTIES);
 
         let old_score = storage::read_score(&env, &user);
         let new_score = old_score
             .checked_sub(amount)
-            .ok_or_else(|| ReputationError::Underflow)
+            .ok_or(ReputationError::Underflow)
             .unwrap();
 
-        if new_score < types::MIN_SCORE {
+        if !(types::MIN_SCORE..=types::MAX_SCORE).contains(&new_score) {
             soroban_sdk::panic_with_error!(&env, ReputationError::OutOfBounds);
         }
 
@@ -77,8 +81,9 @@ impl ReputationContract {
     pub fn set_score(env: Env, updater: Address, user: Address, new_score: u32) {
         updater.require_auth();
         access::require_updater(&env, &updater);
+        access::require_not_paused(&env, types::PAUSE_SET_SCORE);
 
-        if new_score < types::MIN_SCORE || new_score > types::MAX_SCORE {
+        if !(types::MIN_SCORE..=types::MAX_SCORE).contains(&new_score) {
             soroban_sdk::panic_with_error!(&env, ReputationError::OutOfBounds);
         }
 
@@ -89,13 +94,74 @@ impl ReputationContract {
         events::emit_score_changed(&env, &user, old_score, new_score, &reason);
     }
 
-    /// Set or remove an address as an authorized updater
+    /// Set the score penalty applied by `slash`
+    /// Requires authorization from a risk manager
+    pub fn set_default_penalty(env: Env, caller: Address, penalty: u32) {
+        caller.require_auth();
+        access::require_role(&env, Role::RiskManager, &caller);
+
+        if penalty > types::MAX_SCORE {
+            soroban_sdk::panic_with_error!(&env, ReputationError::OutOfBounds);
+        }
+
+        storage::set_default_penalty(&env, penalty);
+    }
+
+    /// Get the score penalty applied by `slash`
+    pub fn get_default_penalty(env: Env) -> u32 {
+        storage::get_default_penalty(&env)
+    }
+
+    /// Set the score rewards applied for repayment behavior
+    /// Requires authorization from a risk manager
+    pub fn set_reward_params(env: Env, caller: Address, params: RewardParams) {
+        caller.require_auth();
+        access::require_role(&env, Role::RiskManager, &caller);
+
+        if params.on_time > types::MAX_SCORE
+            || params.early > types::MAX_SCORE
+            || params.completion > types::MAX_SCORE
+        {
+            soroban_sdk::panic_with_error!(&env, ReputationError::OutOfBounds);
+        }
+
+        storage::set_reward_params(&env, &params);
+    }
+
+    /// Get the score rewards applied for repayment behavior
+    pub fn get_reward_params(env: Env) -> RewardParams {
+        storage::get_reward_params(&env)
+    }
+
+    /// Set the score penalties applied for poor repayment behavior
+    /// Requires authorization from a risk manager
+    pub fn set_penalty_params(env: Env, caller: Address, params: PenaltyParams) {
+        caller.require_auth();
+        access::require_role(&env, Role::RiskManager, &caller);
+
+        if params.late > types::MAX_SCORE
+            || params.missed > types::MAX_SCORE
+            || params.restructure > types::MAX_SCORE
+        {
+            soroban_sdk::panic_with_error!(&env, ReputationError::OutOfBounds);
+        }
+
+        storage::set_penalty_params(&env, &params);
+    }
+
+    /// Get the score penalties applied for poor repayment behavior
+    pub fn get_penalty_params(env: Env) -> PenaltyParams {
+        storage::get_penalty_params(&env)
+    }
+
+    /// Grant or revoke the Updater role of an address
     /// Requires authorization from admin
     pub fn set_updater(env: Env, admin: Address, updater: Address, allowed: bool) {
-        admin.require_auth();
-        access::require_admin(&env, &admin);
-        
-        storage::set_updater(&env, &updater, allowed);
+        if allowed {
+            Self::grant_role(env.clone(), admin, Role::Updater, updater.clone());
+        } else {
+            Self::revoke_role(env.clone(), admin, Role::Updater, updater.clone());
+        }
         events::emit_updater_changed(&env, &updater, allowed);
     }
 
@@ -104,29 +170,386 @@ impl ReputationContract {
         storage::is_updater(&env, &addr)
     }
 
-    /// Set the admin address for this contract
-    /// Requires authorization from current admin (or allows initial setup)
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let old_admin_opt: Option<Address> = env.storage().instance().get(&storage::ADMIN_KEY);
-        
-        if let Some(old_admin) = old_admin_opt {
-            // Admin exists, require current admin authorization
-            old_admin.require_auth();
-            access::require_admin(&env, &old_admin);
-            storage::set_admin(&env, &new_admin);
-            events::emit_admin_changed(&env, &old_admin, &new_admin);
-        } else {
-            // No admin exists, allow setting (initialization)
-            storage::set_admin(&env, &new_admin);
-            let dummy = new_admin.clone();
-            events::emit_admin_changed(&env, &dummy, &new_admin);
+    /// Grant a role to an address
+    /// Requires authorization from admin; once a signer set is configured, use a `GrantRole` proposal
+    pub fn grant_role(env: Env, admin: Address, role: Role, account: Address) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+        access::require_no_governance(&env);
+
+        roles::grant_role(&env, &admin, role, &account);
+    }
+
+    /// Revoke a role from an address
+    /// Requires authorization from admin; once a signer set is configured, use a `RevokeRole` proposal
+    pub fn revoke_role(env: Env, admin: Address, role: Role, account: Address) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+        access::require_no_governance(&env);
+
+        roles::revoke_role(&env, &admin, role, &account);
+    }
+
+    /// Check if an address holds a role, the admin holds every role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match role {
+            Role::Updater => storage::is_updater(&env, &account),
+            _ => roles::has_role(&env, role, &account),
+        }
+    }
+
+    /// Pause every score update
+    /// Requires authorization from a pauser
+    pub fn pause(env: Env, caller: Address) {
+        Self::set_paused(env, caller, types::PAUSE_ALL);
+    }
+
+    /// Resume every paused score update
+    /// Requires authorization from a pauser
+    pub fn unpause(env: Env, caller: Address) {
+        Self::set_paused(env, caller, 0);
+    }
+
+    /// Pause only the score update groups in `flags` and resume the rest
+    /// Requires authorization from a pauser; bits outside PAUSE_ALL are ignored
+    pub fn set_paused(env: Env, caller: Address, flags: u32) {
+        caller.require_auth();
+        access::require_role(&env, Role::Pauser, &caller);
+
+        let flags = flags & types::PAUSE_ALL;
+        let old_flags = storage::get_paused(&env);
+        storage::set_paused(&env, flags);
+        events::emit_pause_changed(&env, &caller, old_flags, flags);
+    }
+
+    /// Get the pause flags currently set, 0 when nothing is paused
+    pub fn get_paused(env: Env) -> u32 {
+        storage::get_paused(&env)
+    }
+
+    /// Set the first admin of this contract and record the hash of the deployed code
+    /// Runs once as part of the deployment, so no one can claim the admin role before it
+    pub fn __constructor(env: Env, admin: Address, wasm_hash: BytesN<32>) {
+        storage::set_admin(&env, &admin);
+        upgrade::set_version(&env, types::CONTRACT_VERSION);
+        upgrade::set_wasm_hash(&env, &wasm_hash);
+    }
+
+    /// Start transferring the admin role to a new address
+    /// Requires authorization from current admin; takes effect once the new address calls accept_admin
+    /// Once a signer set is configured, use a `SetAdmin` proposal instead
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        access::require_no_governance(&env);
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_pending_admin(&env, &new_admin);
+        events::emit_admin_proposed(&env, &admin, &new_admin);
+    }
+
+    /// Complete a pending admin transfer
+    /// Requires authorization from the proposed admin; closed once a signer set is configured
+    pub fn accept_admin(env: Env) {
+        access::require_no_governance(&env);
+        let new_admin = storage::get_pending_admin(&env)
+            .unwrap_or_else(|| soroban_sdk::panic_with_error!(&env, ReputationError::NoPendingAdmin));
+        new_admin.require_auth();
+
+        let old_admin = storage::get_admin(&env);
+        storage::set_admin(&env, &new_admin);
+        storage::remove_pending_admin(&env);
+        events::emit_admin_changed(&env, &old_admin, &new_admin);
+    }
+
+    /// Withdraw a pending admin transfer
+    /// Requires authorization from current admin
+    pub fn cancel_admin_transfer(env: Env) {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let pending_admin = storage::get_pending_admin(&env)
+            .unwrap_or_else(|| soroban_sdk::panic_with_error!(&env, ReputationError::NoPendingAdmin));
+        storage::remove_pending_admin(&env);
+        events::emit_admin_transfer_cancelled(&env, &admin, &pending_admin);
+    }
+
+    /// Configure the first signer set for governance proposals
+    /// Requires authorization from admin; afterwards admin changes and role grants need
+    /// `threshold` signer approvals and the signer set changes through a `SetSigners` proposal
+    pub fn set_signers(env: Env, admin: Address, signers: Vec<Address>, threshold: u32) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        if governance::is_enabled(&env) {
+            soroban_sdk::panic_with_error!(&env, ReputationError::GovernanceRequired);
+        }
+        if !governance::is_valid_signer_set(&signers, threshold) {
+            soroban_sdk::panic_with_error!(&env, ReputationError::InvalidSignerSet);
+        }
+
+        governance::set_signers(&env, &signers, threshold);
+    }
+
+    /// Propose a governance action, counting as the proposer's approval
+    /// Requires authorization from a signer; the action runs right away when one approval is enough
+    /// Returns the proposal ID
+    pub fn propose_action(env: Env, signer: Address, action: GovAction) -> u64 {
+        signer.require_auth();
+        access::require_signer(&env, &signer);
+        Self::validate_action(&env, &action);
+
+        let proposal_id = governance::create_proposal(&env, &signer, &action);
+        let mut proposal = governance::get_proposal(&env, proposal_id)
+            .unwrap_or_else(|| soroban_sdk::panic_with_error!(&env, ReputationError::ProposalNotFound));
+        Self::execute_if_approved(&env, proposal_id, &mut proposal);
+        proposal_id
+    }
+
+    /// Approve a governance proposal
+    /// Requires authorization from a signer; the action runs once approvals from the current
+    /// signers reach the threshold. Returns true when the action was executed
+    pub fn approve(env: Env, signer: Address, proposal_id: u64) -> bool {
+        signer.require_auth();
+        access::require_signer(&env, &signer);
+
+        let mut proposal = governance::get_proposal(&env, proposal_id)
+            .unwrap_or_else(|| soroban_sdk::panic_with_error!(&env, ReputationError::ProposalNotFound));
+        if proposal.executed {
+            soroban_sdk::panic_with_error!(&env, ReputationError::ProposalExecuted);
         }
+        if proposal.approvals.contains(&signer) {
+            soroban_sdk::panic_with_error!(&env, ReputationError::AlreadyApproved);
+        }
+
+        governance::add_approval(&env, proposal_id, &mut proposal, &signer);
+        Self::execute_if_approved(&env, proposal_id, &mut proposal)
+    }
+
+    /// Get a governance proposal and its approvals
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        governance::get_proposal(&env, proposal_id)
+    }
+
+    /// Get the signers allowed to propose and approve governance actions
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        governance::get_signers(&env)
+    }
+
+    /// Get the approvals a governance action needs, 0 when no signer set is configured
+    pub fn get_threshold(env: Env) -> u32 {
+        governance::get_threshold(&env)
     }
 
     /// Get the current admin address
     pub fn get_admin(env: Env) -> Address {
         storage::get_admin(&env)
     }
+
+    /// Get the address proposed as the next admin, if a transfer is pending
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Extend the TTL of a user's score entry
+    /// Anyone can call this to keep a score from being archived
+    pub fn extend_ttl(env: Env, user: Address) {
+        storage::extend_ttl(&env, &types::DataKey::Score(user));
+    }
+
+    /// Move up to `limit` entries of the legacy score and updater maps to per-address storage and roles
+    /// Requires authorization from admin; entries already written per address are kept
+    /// Returns the number of entries left to migrate, the maps are removed once empty
+    pub fn migrate_storage(env: Env, admin: Address, limit: u32) -> u32 {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+
+        let mut scores = storage::read_legacy_scores(&env).unwrap_or_else(|| Map::new(&env));
+        let mut updaters = storage::read_legacy_updaters(&env).unwrap_or_else(|| Map::new(&env));
+        let mut batch = limit;
+
+        for user in scores.keys().iter() {
+            if batch == 0 {
+                break;
+            }
+            if let Some(score) = scores.get(user.clone()) {
+                if !storage::has_entry(&env, &types::DataKey::Score(user.clone())) {
+                    storage::write_score(&env, &user, score);
+                }
+            }
+            scores.remove(user);
+            batch -= 1;
+        }
+
+        for updater in updaters.keys().iter() {
+            if batch == 0 {
+                break;
+            }
+            if !storage::has_updater_entry(&env, &updater) {
+                if updaters.get(updater.clone()).unwrap_or(false) {
+                    roles::grant_role(&env, &admin, Role::Updater, &updater);
+                } else {
+                    roles::revoke_role(&env, &admin, Role::Updater, &updater);
+                }
+            }
+            updaters.remove(updater);
+            batch -= 1;
+        }
+
+        storage::write_legacy_scores(&env, &scores);
+        storage::write_legacy_updaters(&env, &updaters);
+        scores.len() + updaters.len()
+    }
+
+    /// Install new contract code from an uploaded WASM hash
+    /// Requires authorization from admin; once a signer set is configured, use an `Upgrade` proposal instead
+    /// The new code runs from the next call, which should be `migrate`
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        admin.require_auth();
+        access::require_admin(&env, &admin);
+        access::require_no_governance(&env);
+
+        upgrade::upgrade(&env, &new_wasm_hash);
+    }
+
+    /// Bring storage from the stored version up to this code's version
+    /// Anyone can call this after an upgrade; each version bump migrates once
+    /// Returns the new storage version
+    pub fn migrate(env: Env) -> u32 {
+        let from_version = upgrade::get_version(&env);
+        if from_version >= types::CONTRACT_VERSION {
+            soroban_sdk::panic_with_error!(&env, ReputationError::AlreadyMigrated);
+        }
+
+        // Layout changes are applied here, one step per version. Deployments from
+        // before versions were tracked (version 0) share the version 1 layout, their
+        // legacy maps are moved with `migrate_storage`
+
+        upgrade::finish_migration(&env, from_version, types::CONTRACT_VERSION);
+        types::CONTRACT_VERSION
+    }
+
+    /// Get the hash of the installed code, recorded at initialize and on every upgrade
+    pub fn get_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        upgrade::get_wasm_hash(&env)
+    }
+
+    /// Decrease a user's score by a penalty, clamped at MIN_SCORE
+    fn apply_penalty(env: &Env, updater: &Address, user: &Address, amount: u32, reason: Symbol) {
+        updater.require_auth();
+        access::require_updater(env, updater);
+        access::require_not_paused(env, types::PAUSE_PENALTIES);
+
+        let old_score = storage::read_score(env, user);
+        // MIN_SCORE is 0, so saturating at zero clamps the score at MIN_SCORE
+        let new_score = old_score.saturating_sub(amount);
+
+        storage::write_score(env, user, new_score);
+        events::emit_score_changed(env, user, old_score, new_score, &reason);
+    }
+
+    /// Increase a user's score by a reward, clamped at MAX_SCORE
+    fn apply_reward(env: &Env, updater: &Address, user: &Address, amount: u32, reason: Symbol) {
+        updater.require_auth();
+        access::require_updater(env, updater);
+        access::require_not_paused(env, types::PAUSE_REWARDS);
+
+        let old_score = storage::read_score(env, user);
+        let new_score = old_score.saturating_add(amount).min(types::MAX_SCORE);
+
+        storage::write_score(env, user, new_score);
+        events::emit_score_changed(env, user, old_score, new_score, &reason);
+    }
+    /// Check the parameters of a governance action
+    fn validate_action(env: &Env, action: &GovAction) {
+        if let GovAction::SetSigners(signers, threshold) = action {
+            if !governance::is_valid_signer_set(signers, *threshold) {
+                soroban_sdk::panic_with_error!(env, ReputationError::InvalidSignerSet);
+            }
+        }
+    }
+
+    /// Run a proposal's action if it has enough approvals, returns true when it ran
+    fn execute_if_approved(env: &Env, proposal_id: u64, proposal: &mut Proposal) -> bool {
+        if !governance::is_approved(env, proposal) {
+            return false;
+        }
+
+        Self::validate_action(env, &proposal.action);
+        governance::mark_executed(env, proposal_id, proposal);
+
+        let contract = env.current_contract_address();
+        match proposal.action.clone() {
+            GovAction::SetAdmin(new_admin) => {
+                let old_admin = storage::get_admin(env);
+                storage::set_admin(env, &new_admin);
+                storage::remove_pending_admin(env);
+                events::emit_admin_changed(env, &old_admin, &new_admin);
+            }
+            GovAction::GrantRole(role, account) => roles::grant_role(env, &contract, role, &account),
+            GovAction::RevokeRole(role, account) => roles::revoke_role(env, &contract, role, &account),
+            GovAction::SetSigners(signers, threshold) => governance::set_signers(env, &signers, threshold),
+            GovAction::Upgrade(new_wasm_hash) => upgrade::upgrade(env, &new_wasm_hash),
+        }
+        true
+    }
+}
+
+#[contractimpl]
+impl ReputationInterface for ReputationContract {
+    /// Get the reputation score for a user
+    fn get_score(env: Env, user: Address) -> u32 {
+        storage::read_score(&env, &user)
+    }
+
+    /// Apply the default penalty to a user's reputation score
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    /// The score is clamped at MIN_SCORE instead of underflowing
+    fn slash(env: Env, updater: Address, user: Address) {
+        let penalty = storage::get_default_penalty(&env);
+        Self::apply_penalty(&env, &updater, &user, penalty, symbol_short!("default"));
+    }
+
+    /// Reward a user for an installment paid on time
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    fn reward_on_time(env: Env, updater: Address, user: Address) {
+        let amount = storage::get_reward_params(&env).on_time;
+        Self::apply_reward(&env, &updater, &user, amount, symbol_short!("on_time"));
+    }
+
+    /// Reward a user for an installment paid early
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    fn reward_early(env: Env, updater: Address, user: Address) {
+        let amount = storage::get_reward_params(&env).early;
+        Self::apply_reward(&env, &updater, &user, amount, symbol_short!("early"));
+    }
+
+    /// Reward a user for fully repaying a loan
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    fn reward_completion(env: Env, updater: Address, user: Address) {
+        let amount = storage::get_reward_params(&env).completion;
+        Self::apply_reward(&env, &updater, &user, amount, symbol_short!("complete"));
+    }
+
+    /// Penalize a user for an installment paid past its grace period
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    fn penalize_late(env: Env, updater: Address, user: Address) {
+        let amount = storage::get_penalty_params(&env).late;
+        Self::apply_penalty(&env, &updater, &user, amount, symbol_short!("late"));
+    }
+
+    /// Penalize a user for an installment still unpaid well after its due date
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    fn penalize_missed(env: Env, updater: Address, user: Address) {
+        let amount = storage::get_penalty_params(&env).missed;
+        Self::apply_penalty(&env, &updater, &user, amount, symbol_short!("missed"));
+    }
+
+    /// Penalize a user whose loan was rescheduled instead of defaulting
+    /// Requires authorization from an updater (e.g. the CreditLine contract)
+    fn penalize_restructure(env: Env, updater: Address, user: Address) {
+        let amount = storage::get_penalty_params(&env).restructure;
+        Self::apply_penalty(&env, &updater, &user, amount, symbol_short!("restruct"));
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,21 @@
+use common::{roles, Role};
 use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
 
+use crate::types::{
+    DataKey, PenaltyParams, RewardParams, DEFAULT_COMPLETION_REWARD, DEFAULT_EARLY_REWARD,
+    DEFAULT_LATE_PENALTY, DEFAULT_MISSED_PENALTY, DEFAULT_ON_TIME_REWARD, DEFAULT_PENALTY,
+    DEFAULT_RESTRUCTURE_PENALTY, SCORE_TTL_EXTEND_TO, SCORE_TTL_THRESHOLD,
+};
+
 // Storage keys for the reputation contract
-pub const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+pub use common::ADMIN_KEY;
+pub const PENDING_ADMIN_KEY: Symbol = symbol_short!("PENDADMIN");
 pub const UPDATERS_MAP: Symbol = symbol_short!("UPDATERS");
 pub const SCORES_MAP: Symbol = symbol_short!("SCORES");
+pub const DEFAULT_PENALTY_KEY: Symbol = symbol_short!("DEFPNLTY");
+pub const REWARDS_KEY: Symbol = symbol_short!("REWARDS");
+pub const PENALTIES_KEY: Symbol = symbol_short!("PENALTIES");
+pub const PAUSED_KEY: Symbol = symbol_short!("PAUSED");
 
 /// Get the admin address from storage
 pub fn get_admin(env: &Env) -> Address {
@@ -18,54 +30,160 @@ pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&ADMIN_KEY, admin);
 }
 
-/// Read a user's reputation score from storage
+/// Get the address proposed as the next admin, if any
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&PENDING_ADMIN_KEY)
+}
+
+/// Set the address proposed as the next admin
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&PENDING_ADMIN_KEY, admin);
+}
+
+/// Clear the proposed admin
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().instance().remove(&PENDING_ADMIN_KEY);
+}
+
+/// Get the pause flags currently set
+pub fn get_paused(env: &Env) -> u32 {
+    env.storage().instance().get(&PAUSED_KEY).unwrap_or(0)
+}
+
+/// Set the pause flags
+pub fn set_paused(env: &Env, flags: u32) {
+    env.storage().instance().set(&PAUSED_KEY, &flags);
+}
+
+/// Read a user's reputation score from storage, extending its TTL when found
+/// Falls back to the legacy instance map for scores not migrated yet
 pub fn read_score(env: &Env, user: &Address) -> u32 {
-    let scores: Map<Address, u32> = env
-        .storage()
-        .instance()
-        .get(&SCORES_MAP)
-        .unwrap_or_else(|| Map::new(env));
-    
-    scores.get(user.clone()).unwrap_or(0)
+    let key = DataKey::Score(user.clone());
+    let score: Option<u32> = env.storage().persistent().get(&key);
+
+    match score {
+        Some(score) => {
+            extend_ttl(env, &key);
+            score
+        }
+        None => read_legacy_scores(env)
+            .and_then(|scores| scores.get(user.clone()))
+            .unwrap_or(0),
+    }
 }
 
 /// Write a user's reputation score to storage
 pub fn write_score(env: &Env, user: &Address, score: u32) {
-    let mut scores: Map<Address, u32> = env
-        .storage()
-        .instance()
-        .get(&SCORES_MAP)
-        .unwrap_or_else(|| Map::new(env));
-    
-    scores.set(user.clone(), score);
-    env.storage().instance().set(&SCORES_MAP, &scores);
+    let key = DataKey::Score(user.clone());
+    env.storage().persistent().set(&key, &score);
+    extend_ttl(env, &key);
 }
 
-/// Check if an address is an authorized updater
+/// Check if an address holds the Updater role
+/// Falls back to the legacy instance map for updaters not migrated yet
 pub fn is_updater(env: &Env, addr: &Address) -> bool {
-    let updaters: Map<Address, bool> = env
-        .storage()
-        .instance()
-        .get(&UPDATERS_MAP)
-        .unwrap_or_else(|| Map::new(env));
-    
-    updaters.get(addr.clone()).unwrap_or(false)
+    if roles::is_admin(env, addr) {
+        return true;
+    }
+
+    match roles::role_entry(env, Role::Updater, addr) {
+        Some(granted) => granted,
+        None => read_legacy_updaters(env)
+            .and_then(|updaters| updaters.get(addr.clone()))
+            .unwrap_or(false),
+    }
 }
 
-/// Set an address as an authorized updater
-pub fn set_updater(env: &Env, updater: &Address, allowed: bool) {
-    let mut updaters: Map<Address, bool> = env
-        .storage()
-        .instance()
-        .get(&UPDATERS_MAP)
-        .unwrap_or_else(|| Map::new(env));
-    
-    if allowed {
-        updaters.set(updater.clone(), true);
+/// Check whether the Updater role was ever granted to or revoked from an address
+pub fn has_updater_entry(env: &Env, addr: &Address) -> bool {
+    roles::role_entry(env, Role::Updater, addr).is_some()
+}
+
+/// Check whether a per-address entry exists
+pub fn has_entry(env: &Env, key: &DataKey) -> bool {
+    env.storage().persistent().has(key)
+}
+
+/// Extend the TTL of a per-address entry if it exists
+pub fn extend_ttl(env: &Env, key: &DataKey) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, SCORE_TTL_THRESHOLD, SCORE_TTL_EXTEND_TO);
+    }
+}
+
+/// Read the legacy map that held every score in instance storage
+pub fn read_legacy_scores(env: &Env) -> Option<Map<Address, u32>> {
+    env.storage().instance().get(&SCORES_MAP)
+}
+
+/// Read the legacy map that held every updater in instance storage
+pub fn read_legacy_updaters(env: &Env) -> Option<Map<Address, bool>> {
+    env.storage().instance().get(&UPDATERS_MAP)
+}
+
+/// Write back the scores left to migrate, removing the legacy map once it is empty
+pub fn write_legacy_scores(env: &Env, scores: &Map<Address, u32>) {
+    if scores.is_empty() {
+        env.storage().instance().remove(&SCORES_MAP);
     } else {
-        updaters.remove(updater.clone());
+        env.storage().instance().set(&SCORES_MAP, scores);
     }
-    
-    env.storage().instance().set(&UPDATERS_MAP, &updaters);
 }
 
+/// Write back the updaters left to migrate, removing the legacy map once it is empty
+pub fn write_legacy_updaters(env: &Env, updaters: &Map<Address, bool>) {
+    if updaters.is_empty() {
+        env.storage().instance().remove(&UPDATERS_MAP);
+    } else {
+        env.storage().instance().set(&UPDATERS_MAP, updaters);
+    }
+}
+
+/// Get the score penalty applied on loan default
+pub fn get_default_penalty(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DEFAULT_PENALTY_KEY)
+        .unwrap_or(DEFAULT_PENALTY)
+}
+
+/// Set the score penalty applied on loan default
+pub fn set_default_penalty(env: &Env, penalty: u32) {
+    env.storage().instance().set(&DEFAULT_PENALTY_KEY, &penalty);
+}
+
+/// Get the score rewards applied for repayment behavior
+pub fn get_reward_params(env: &Env) -> RewardParams {
+    env.storage()
+        .instance()
+        .get(&REWARDS_KEY)
+        .unwrap_or(RewardParams {
+            on_time: DEFAULT_ON_TIME_REWARD,
+            early: DEFAULT_EARLY_REWARD,
+            completion: DEFAULT_COMPLETION_REWARD,
+        })
+}
+
+/// Set the score rewards applied for repayment behavior
+pub fn set_reward_params(env: &Env, params: &RewardParams) {
+    env.storage().instance().set(&REWARDS_KEY, params);
+}
+
+/// Get the score penalties applied for late repayment behavior
+pub fn get_penalty_params(env: &Env) -> PenaltyParams {
+    env.storage()
+        .instance()
+        .get(&PENALTIES_KEY)
+        .unwrap_or(PenaltyParams {
+            late: DEFAULT_LATE_PENALTY,
+            missed: DEFAULT_MISSED_PENALTY,
+            restructure: DEFAULT_RESTRUCTURE_PENALTY,
+        })
+}
+
+/// Set the score penalties applied for late repayment behavior
+pub fn set_penalty_params(env: &Env, params: &PenaltyParams) {
+    env.storage().instance().set(&PENALTIES_KEY, params);
+}
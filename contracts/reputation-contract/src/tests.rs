@@ -1,28 +1,114 @@
 #![cfg(test)]
+extern crate std;
 
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{
+        storage::Persistent as _, Address as _, AuthorizedFunction, AuthorizedInvocation, Events,
+        Ledger,
+    },
+    vec, Address, BytesN, Env, IntoVal, Map, Symbol,
+};
+
+use common::roles::RoleKey;
 
 use crate::ReputationContract;
 use crate::ReputationContractClient;
+use crate::{GovAction, PenaltyParams, RewardParams, ReputationError, Role, PAUSE_ALL, PAUSE_PENALTIES};
+use crate::storage;
+use crate::types::{DataKey, CONTRACT_VERSION, SCORE_TTL_EXTEND_TO};
+
+// Tests register the contract natively, so any hash stands in for the deployed code
+fn deployed_wasm_hash(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[1; 32])
+}
 
-/// Test: Sets the contract admin
-/// Verifies that an address can be assigned as the contract administrator.
+/// Test: Sets the contract admin at deployment
+/// Verifies that the constructor assigns the contract administrator, so no caller can claim it first.
 /// Receives: Admin Address. Returns: void. Validates that the admin is stored correctly.
 #[test]
-fn it_sets_admin() {
+fn it_sets_admin_at_deployment() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let retrieved_admin = client.get_admin();
     assert_eq!(retrieved_admin, admin);
 }
 
+/// Test: Transfers admin in two steps
+/// Verifies that a proposed admin only takes over after accepting.
+/// Receives: Admin Address, New Admin Address. Returns: void. Validates the admin after each step and the ADMINCHGD event.
+#[test]
+fn it_transfers_admin_in_two_steps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("ADMINCHGD"),).into_val(&env),
+                (admin.clone(), new_admin.clone()).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+/// Test: Cancels a pending admin transfer
+/// Verifies that the admin can withdraw a transfer before it is accepted.
+/// Receives: Admin Address, mistyped Address. Returns: NoPendingAdmin error (#8) on accept. Validates the admin is unchanged.
+#[test]
+fn it_cancels_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    client.propose_admin(&Address::generate(&env));
+    client.cancel_admin_transfer();
+
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(ReputationError::NoPendingAdmin.into()))
+    );
+    assert_eq!(client.get_admin(), admin);
+}
+
 /// Test: Gets the contract admin
 /// Verifies that the current contract administrator can be queried.
 /// Receives: nothing. Returns: Admin Address. Validates that it returns the correct address.
@@ -31,11 +117,9 @@ fn it_gets_admin() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let retrieved = client.get_admin();
     assert_eq!(retrieved, admin);
@@ -49,16 +133,14 @@ fn it_sets_updater() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     client.set_updater(&admin, &updater, &true);
     
-    assert_eq!(client.is_updater(&updater), true);
+    assert!(client.is_updater(&updater));
 }
 
 /// Test: Checks updater permissions
@@ -69,19 +151,17 @@ fn it_checks_updater() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     let non_updater = Address::generate(&env);
     
     client.set_updater(&admin, &updater, &true);
     
-    assert_eq!(client.is_updater(&updater), true);
-    assert_eq!(client.is_updater(&non_updater), false);
+    assert!(client.is_updater(&updater));
+    assert!(!client.is_updater(&non_updater));
 }
 
 /// Test: Gets the reputation score
@@ -92,11 +172,9 @@ fn it_gets_score() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     client.set_updater(&admin, &updater, &true);
@@ -119,11 +197,9 @@ fn it_increases_score() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     client.set_updater(&admin, &updater, &true);
@@ -144,11 +220,9 @@ fn it_decreases_score() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     client.set_updater(&admin, &updater, &true);
@@ -169,11 +243,9 @@ fn it_sets_score() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     client.set_updater(&admin, &updater, &true);
@@ -195,12 +267,11 @@ fn it_sets_score() {
 fn it_prevents_unauthorized_updates() {
     let env = Env::default();
     
-    let contract_id = env.register(ReputationContract, ());
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
     let client = ReputationContractClient::new(&env, &contract_id);
     
-    let admin = Address::generate(&env);
     env.mock_all_auths();
-    client.set_admin(&admin);
     
     let user = Address::generate(&env);
     let unauthorized = Address::generate(&env);
@@ -218,11 +289,9 @@ fn it_enforces_score_bounds() {
     let env = Env::default();
     env.mock_all_auths();
     
-    let contract_id = env.register(ReputationContract, ());
-    let client = ReputationContractClient::new(&env, &contract_id);
-    
     let admin = Address::generate(&env);
-    client.set_admin(&admin);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
     
     let updater = Address::generate(&env);
     client.set_updater(&admin, &updater, &true);
@@ -234,16 +303,864 @@ fn it_enforces_score_bounds() {
 }
 
 /// Test: Gets the contract version
-/// Verifies that the contract returns its version identifier correctly.
-/// Receives: nothing. Returns: Symbol "v1_0_0". Useful for verifying deployed version in production.
+/// Verifies that the constructor records the storage version and the deployed code hash.
+/// Receives: Admin Address, WASM hash. Returns: CONTRACT_VERSION. Useful for verifying deployed version in production.
 #[test]
 fn it_gets_version() {
     let env = Env::default();
-    
-    let contract_id = env.register(ReputationContract, ());
+    env.mock_all_auths();
+
+    let contract_id = env.register(ReputationContract, (Address::generate(&env), deployed_wasm_hash(&env)));
     let client = ReputationContractClient::new(&env, &contract_id);
-    
-    let version = client.get_version();
-    assert_eq!(version, symbol_short!("v1_0_0"));
+
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert_eq!(client.get_wasm_hash(), Some(deployed_wasm_hash(&env)));
+}
+
+/// Test: Slashes a score on default
+/// Verifies that an updater can apply the default penalty to a user's score.
+/// Receives: Updater Address, User Address. Returns: void. Validates the score drops by the default penalty.
+#[test]
+fn it_slashes_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &75);
+
+    client.slash(&updater, &user);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("SCORECHGD"), user.clone()).into_val(&env),
+                (75u32, 50u32, symbol_short!("default")).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_score(&user), 50);
+}
+
+/// Test: Slash clamps at the minimum score
+/// Verifies that slashing a score lower than the penalty clamps it at MIN_SCORE instead of underflowing.
+/// Receives: Updater Address, User Address with low score. Returns: void. Validates the score becomes 0.
+#[test]
+fn it_clamps_slash_at_min_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &10);
+
+    client.slash(&updater, &user);
+
+    assert_eq!(client.get_score(&user), 0);
+}
+
+/// Test: Uses the configured default penalty
+/// Verifies that the admin can change the penalty applied by slash.
+/// Receives: Admin Address, u32 penalty. Returns: void. Validates slash uses the new penalty.
+#[test]
+fn it_sets_default_penalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    assert_eq!(client.get_default_penalty(), 25);
+    client.set_default_penalty(&admin, &30);
+    assert_eq!(client.get_default_penalty(), 30);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &80);
+    client.slash(&updater, &user);
+
+    assert_eq!(client.get_score(&user), 50);
+}
+
+/// Test: Prevents unauthorized slashing
+/// Verifies that only authorized updaters can slash scores.
+/// Receives: Unauthorized Address. Returns: panic with NotUpdater error (#2).
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn it_prevents_unauthorized_slash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let unauthorized = Address::generate(&env);
+    client.slash(&unauthorized, &Address::generate(&env));
+}
+
+/// Test: Prevents non-risk managers from changing the default penalty
+/// Receives: Address without the RiskManager role. Returns: panic with MissingRole error (#9).
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn it_prevents_non_admin_setting_default_penalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    client.set_default_penalty(&Address::generate(&env), &30);
+}
+
+/// Test: Rewards repayment behavior
+/// Verifies that each reward hook applies its own configured bonus.
+/// Receives: Updater Address, User Address. Returns: void. Validates on-time, early and completion bonuses.
+#[test]
+fn it_rewards_repayment_behavior() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &50);
+
+    client.reward_on_time(&updater, &user);
+    assert_eq!(client.get_score(&user), 53);
+
+    client.reward_early(&updater, &user);
+    assert_eq!(client.get_score(&user), 60);
+
+    client.reward_completion(&updater, &user);
+    assert_eq!(client.get_score(&user), 72);
+}
+
+/// Test: Tags each reward with its own reason
+/// Verifies that the SCORECHGD event carries a distinct reason symbol per reward.
+/// Receives: Updater Address, User Address. Returns: void. Validates the event reason.
+#[test]
+fn it_emits_reward_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &50);
+
+    client.reward_early(&updater, &user);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("SCORECHGD"), user.clone()).into_val(&env),
+                (50u32, 57u32, symbol_short!("early")).into_val(&env),
+            )
+        ]
+    );
+}
+
+/// Test: Rewards clamp at the maximum score
+/// Verifies that rewards never push a score above MAX_SCORE.
+/// Receives: Updater Address, User Address with high score. Returns: void. Validates the score becomes 100.
+#[test]
+fn it_clamps_rewards_at_max_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &95);
+
+    client.reward_completion(&updater, &user);
+
+    assert_eq!(client.get_score(&user), 100);
+}
+
+/// Test: Uses the configured reward parameters
+/// Verifies that the admin can change the reward amounts.
+/// Receives: Admin Address, RewardParams. Returns: void. Validates rewards use the new values.
+#[test]
+fn it_sets_reward_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let params = RewardParams {
+        on_time: 5,
+        early: 10,
+        completion: 15,
+    };
+    client.set_reward_params(&admin, &params);
+    assert_eq!(client.get_reward_params(), params);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &50);
+    client.reward_on_time(&updater, &user);
+
+    assert_eq!(client.get_score(&user), 55);
+}
+
+/// Test: Prevents unauthorized rewards
+/// Verifies that only authorized updaters can reward users.
+/// Receives: Unauthorized Address. Returns: panic with NotUpdater error (#2).
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn it_prevents_unauthorized_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    client.reward_completion(&Address::generate(&env), &Address::generate(&env));
+}
+
+/// Test: Late repayment penalties reduce score
+/// Verifies that penalize_late, penalize_missed and penalize_restructure apply their default penalties.
+/// Receives: Updater Address, User Address. Returns: void. Validates score after each penalty.
+#[test]
+fn it_penalizes_late_repayment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &50);
+
+    client.penalize_late(&updater, &user);
+    assert_eq!(client.get_score(&user), 47);
+
+    client.penalize_missed(&updater, &user);
+    assert_eq!(client.get_score(&user), 40);
+
+    client.penalize_restructure(&updater, &user);
+    assert_eq!(client.get_score(&user), 30);
+}
+
+/// Test: Uses the configured penalty parameters
+/// Verifies that the admin can change the late repayment penalties.
+/// Receives: Admin Address, PenaltyParams. Returns: void. Validates penalties use the new values.
+#[test]
+fn it_sets_penalty_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let params = PenaltyParams { late: 2, missed: 10, restructure: 15 };
+    client.set_penalty_params(&admin, &params);
+    assert_eq!(client.get_penalty_params(), params);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &5);
+    client.penalize_missed(&updater, &user);
+
+    // Clamped at MIN_SCORE
+    assert_eq!(client.get_score(&user), 0);
+}
+
+/// Test: Prevents unauthorized late penalties
+/// Verifies that only authorized updaters can penalize users.
+/// Receives: Unauthorized Address. Returns: panic with NotUpdater error (#2).
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn it_prevents_unauthorized_late_penalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    client.penalize_late(&Address::generate(&env), &Address::generate(&env));
+}
+
+/// Test: Stores scores and updaters under per-address keys
+/// Verifies that writes go to persistent storage with an extended TTL.
+/// Receives: Updater Address, User Address. Returns: void. Validates the persistent entries.
+#[test]
+fn it_stores_scores_per_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &60);
+
+    env.as_contract(&contract_id, || {
+        let score_key = DataKey::Score(user.clone());
+        assert_eq!(env.storage().persistent().get::<_, u32>(&score_key), Some(60));
+        assert_eq!(env.storage().persistent().get_ttl(&score_key), SCORE_TTL_EXTEND_TO);
+        assert!(env
+            .storage()
+            .persistent()
+            .has(&RoleKey::Role(Role::Updater, updater.clone())));
+        assert!(!env.storage().instance().has(&storage::SCORES_MAP));
+        assert!(!env.storage().instance().has(&storage::UPDATERS_MAP));
+    });
+}
+
+/// Test: Anyone can extend a score's TTL
+/// Verifies that extend_ttl bumps the entry back to the full TTL.
+/// Receives: User Address. Returns: void. Validates the TTL after ledgers pass.
+#[test]
+fn it_extends_score_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &60);
+
+    // Keep the instance alive, then move past the TTL threshold of the score entry
+    env.as_contract(&contract_id, || {
+        env.storage().instance().extend_ttl(SCORE_TTL_EXTEND_TO, SCORE_TTL_EXTEND_TO);
+    });
+    let sequence = env.ledger().sequence();
+    env.ledger().set_sequence_number(sequence + SCORE_TTL_EXTEND_TO - 100);
+
+    client.extend_ttl(&user);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            env.storage().persistent().get_ttl(&DataKey::Score(user.clone())),
+            SCORE_TTL_EXTEND_TO
+        );
+    });
 }
 
+/// Test: Migrates the legacy score and updater maps
+/// Verifies that existing instance maps are copied to per-address keys in batches and removed once empty.
+/// Receives: Admin Address, batch limit. Returns: number of entries left. Validates scores and updaters survive.
+#[test]
+fn it_migrates_legacy_maps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    let user = Address::generate(&env);
+    let migrated_user = Address::generate(&env);
+
+    // Newer per-address score must win over the legacy value
+    client.set_updater(&admin, &updater, &true);
+    client.set_score(&updater, &migrated_user, &90);
+
+    env.as_contract(&contract_id, || {
+        let mut scores: Map<Address, u32> = Map::new(&env);
+        scores.set(user.clone(), 70);
+        scores.set(migrated_user.clone(), 40);
+        env.storage().instance().set(&storage::SCORES_MAP, &scores);
+
+        let mut updaters: Map<Address, bool> = Map::new(&env);
+        updaters.set(updater.clone(), true);
+        env.storage().instance().set(&storage::UPDATERS_MAP, &updaters);
+    });
+
+    // Legacy scores stay readable until migrated
+    assert_eq!(client.get_score(&user), 70);
+
+    // The first batch empties the score map, the updater map is left for the next one
+    assert_eq!(client.migrate_storage(&admin, &2), 1);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&storage::SCORES_MAP));
+        assert!(env.storage().instance().has(&storage::UPDATERS_MAP));
+    });
+    assert_eq!(client.migrate_storage(&admin, &2), 0);
+
+    assert_eq!(client.get_score(&user), 70);
+    assert_eq!(client.get_score(&migrated_user), 90);
+    assert!(client.is_updater(&updater));
+
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().persistent().has(&DataKey::Score(user.clone())));
+        assert!(!env.storage().instance().has(&storage::SCORES_MAP));
+        assert!(!env.storage().instance().has(&storage::UPDATERS_MAP));
+    });
+}
+
+/// Test: Prevents non-admin storage migration
+/// Verifies that only the admin can run the migration.
+/// Receives: Non-admin Address. Returns: panic with NotAdmin error (#1).
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn it_prevents_non_admin_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    client.migrate_storage(&Address::generate(&env), &10);
+}
+
+/// Test: Pausing halts every score update
+/// Verifies that pause emits PAUSECHG and blocks updates until unpause.
+/// Receives: Admin Address, Updater Address. Returns: ContractPaused error (#6). Validates updates resume after unpause.
+#[test]
+fn it_pauses_score_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &60);
+
+    client.pause(&admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("PAUSECHG"), admin.clone()).into_val(&env),
+                (0u32, PAUSE_ALL).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_paused(), PAUSE_ALL);
+
+    let paused = Err(Ok(ReputationError::ContractPaused.into()));
+    assert_eq!(client.try_set_score(&updater, &user, &70), paused);
+    assert_eq!(client.try_increase_score(&updater, &user, &5), paused);
+    assert_eq!(client.try_reward_completion(&updater, &user), paused);
+    assert_eq!(client.try_slash(&updater, &user), paused);
+    assert_eq!(client.get_score(&user), 60);
+
+    client.unpause(&admin);
+    assert_eq!(client.get_paused(), 0);
+    client.slash(&updater, &user);
+    assert_eq!(client.get_score(&user), 35);
+}
+
+/// Test: Pauses only the selected score updates
+/// Verifies that penalties can be paused while rewards still apply.
+/// Receives: Admin Address, PAUSE_PENALTIES flag. Returns: void. Validates rewards still change the score.
+#[test]
+fn it_pauses_penalties_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    client.set_updater(&admin, &updater, &true);
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &60);
+
+    client.set_paused(&admin, &PAUSE_PENALTIES);
+
+    assert_eq!(
+        client.try_penalize_late(&updater, &user),
+        Err(Ok(ReputationError::ContractPaused.into()))
+    );
+    client.reward_on_time(&updater, &user);
+    assert_eq!(client.get_score(&user), 63);
+}
+
+/// Test: Prevents pausing without the Pauser role
+/// Verifies that only a pauser or the admin can pause the contract.
+/// Receives: Address without the Pauser role. Returns: panic with MissingRole error (#9).
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn it_prevents_non_admin_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    client.pause(&Address::generate(&env));
+}
+
+/// Test: Grants and revokes roles
+/// Verifies that the admin can grant a role, that it emits ROLEGRNT and that revoking removes it.
+/// Receives: Admin Address, RiskManager Address. Returns: void. Validates has_role and the role events.
+#[test]
+fn it_grants_and_revokes_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let manager = Address::generate(&env);
+    assert!(!client.has_role(&Role::RiskManager, &manager));
+    assert!(client.has_role(&Role::RiskManager, &admin));
+
+    client.grant_role(&admin, &Role::RiskManager, &manager);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("ROLEGRNT"), Role::RiskManager, manager.clone()).into_val(&env),
+                admin.clone().into_val(&env),
+            )
+        ]
+    );
+    assert!(client.has_role(&Role::RiskManager, &manager));
+    assert!(!client.has_role(&Role::Pauser, &manager));
+
+    // A risk manager can tune penalties but not pause
+    client.set_default_penalty(&manager, &30);
+    assert_eq!(client.get_default_penalty(), 30);
+    assert_eq!(
+        client.try_pause(&manager),
+        Err(Ok(ReputationError::MissingRole.into()))
+    );
+
+    client.revoke_role(&admin, &Role::RiskManager, &manager);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("ROLERVKD"), Role::RiskManager, manager.clone()).into_val(&env),
+                admin.clone().into_val(&env),
+            )
+        ]
+    );
+    assert!(!client.has_role(&Role::RiskManager, &manager));
+    assert_eq!(
+        client.try_set_default_penalty(&manager, &40),
+        Err(Ok(ReputationError::MissingRole.into()))
+    );
+}
+
+/// Test: Admin role holders manage roles
+/// Verifies that an address granted the Admin role can grant roles, and that other roles cannot.
+/// Receives: Admin Address, Pauser Address. Returns: NotAdmin error (#1) for the pauser. Validates the delegated grant.
+#[test]
+fn it_lets_admin_role_holders_grant_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let co_admin = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    client.grant_role(&admin, &Role::Admin, &co_admin);
+    client.grant_role(&co_admin, &Role::Pauser, &pauser);
+
+    client.pause(&pauser);
+    assert_eq!(client.get_paused(), PAUSE_ALL);
+
+    assert_eq!(
+        client.try_grant_role(&pauser, &Role::Updater, &pauser),
+        Err(Ok(ReputationError::NotAdmin.into()))
+    );
+}
+
+/// Test: Updater role follows the legacy updater map
+/// Verifies that updaters stored before roles existed keep their access until revoked.
+/// Receives: Admin Address, legacy Updater Address. Returns: NotUpdater error (#2) after revoking. Validates the fallback.
+#[test]
+fn it_reads_legacy_updater_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let updater = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let mut updaters: Map<Address, bool> = Map::new(&env);
+        updaters.set(updater.clone(), true);
+        env.storage().instance().set(&storage::UPDATERS_MAP, &updaters);
+    });
+
+    assert!(client.is_updater(&updater));
+    assert!(client.has_role(&Role::Updater, &updater));
+
+    let user = Address::generate(&env);
+    client.set_score(&updater, &user, &60);
+
+    client.revoke_role(&admin, &Role::Updater, &updater);
+    assert!(!client.is_updater(&updater));
+    assert_eq!(
+        client.try_set_score(&updater, &user, &70),
+        Err(Ok(ReputationError::NotUpdater.into()))
+    );
+}
+
+/// Test: Grants roles through signer approvals
+/// Verifies that once signers are configured, role grants need the threshold of approvals.
+/// Receives: Admin Address, three signers with threshold 2. Returns: GovernanceRequired error (#15) for direct grants. Validates the updater after the second approval.
+#[test]
+fn it_grants_roles_through_signer_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    client.set_signers(&admin, &vec![&env, alice.clone(), bob.clone(), carol.clone()], &2);
+    assert_eq!(client.get_threshold(), 2);
+
+    let updater = Address::generate(&env);
+    assert_eq!(
+        client.try_set_updater(&admin, &updater, &true),
+        Err(Ok(ReputationError::GovernanceRequired.into()))
+    );
+    assert_eq!(
+        client.try_propose_admin(&Address::generate(&env)),
+        Err(Ok(ReputationError::GovernanceRequired.into()))
+    );
+
+    let proposal_id = client.propose_action(&alice, &GovAction::GrantRole(Role::Updater, updater.clone()));
+    assert!(!client.is_updater(&updater));
+    assert_eq!(
+        client.try_approve(&alice, &proposal_id),
+        Err(Ok(ReputationError::AlreadyApproved.into()))
+    );
+    assert_eq!(
+        client.try_approve(&admin, &proposal_id),
+        Err(Ok(ReputationError::NotSigner.into()))
+    );
+
+    assert!(client.approve(&carol, &proposal_id));
+    assert!(client.is_updater(&updater));
+    assert!(client.get_proposal(&proposal_id).unwrap().executed);
+    assert_eq!(
+        client.try_approve(&bob, &proposal_id),
+        Err(Ok(ReputationError::ProposalExecuted.into()))
+    );
+}
+
+/// Test: Changes admin and signers through proposals
+/// Verifies that SetSigners and SetAdmin run once approved and reject invalid signer sets.
+/// Receives: Two signers with threshold 2. Returns: InvalidSignerSet error (#14) for a threshold above the signer count. Validates the new signers and admin.
+#[test]
+fn it_changes_admin_and_signers_through_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    assert_eq!(
+        client.try_set_signers(&admin, &vec![&env, alice.clone()], &2),
+        Err(Ok(ReputationError::InvalidSignerSet.into()))
+    );
+    client.set_signers(&admin, &vec![&env, alice.clone(), bob.clone()], &2);
+    assert_eq!(
+        client.try_set_signers(&admin, &vec![&env, alice.clone()], &1),
+        Err(Ok(ReputationError::GovernanceRequired.into()))
+    );
+
+    let proposal_id = client.propose_action(&bob, &GovAction::SetSigners(vec![&env, alice.clone()], 1));
+    assert!(client.approve(&alice, &proposal_id));
+    assert_eq!(client.get_signers(), vec![&env, alice.clone()]);
+    assert_eq!(client.get_threshold(), 1);
+
+    // A single remaining signer runs actions as soon as they are proposed
+    let new_admin = Address::generate(&env);
+    client.propose_action(&alice, &GovAction::SetAdmin(new_admin.clone()));
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+/// Test: Closes pending admin transfers once signers are set
+/// Verifies that a transfer proposed before set_signers can't be accepted afterwards.
+/// Receives: Pending admin, then a single signer. Returns: GovernanceRequired error (#15). Validates the admin is unchanged.
+#[test]
+fn it_rejects_pending_admin_after_signers_are_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    client.set_signers(&admin, &vec![&env, Address::generate(&env)], &1);
+
+    assert_eq!(client.try_accept_admin(), Err(Ok(ReputationError::GovernanceRequired.into())));
+    assert_eq!(client.get_admin(), admin);
+}
+
+// Smallest WASM the host accepts: no code, only the env meta section for protocol 22
+const EMPTY_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x00, 0x1e, 0x11, b'c', b'o', b'n', b't', b'r', b'a', b'c', b't',
+    b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0', 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00,
+    0x00, 0x00,
+];
+
+/// Test: Upgrades the contract code through a proposal
+/// Verifies that direct upgrades need the admin and that an approved Upgrade proposal installs the new code.
+/// Receives: Uploaded WASM hash. Returns: NotAdmin error (#1), then GovernanceRequired error (#15) once signers are set. Validates the UPGRADED event and stored hash.
+#[test]
+fn it_upgrades_through_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    let attacker = Address::generate(&env);
+    assert_eq!(client.get_wasm_hash(), Some(deployed_wasm_hash(&env)));
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(soroban_sdk::Bytes::from_slice(&env, EMPTY_WASM));
+    assert_eq!(
+        client.try_upgrade(&attacker, &new_wasm_hash),
+        Err(Ok(ReputationError::NotAdmin.into()))
+    );
+
+    let alice = Address::generate(&env);
+    client.set_signers(&admin, &vec![&env, alice.clone()], &1);
+    assert_eq!(
+        client.try_upgrade(&admin, &new_wasm_hash),
+        Err(Ok(ReputationError::GovernanceRequired.into()))
+    );
+
+    client.propose_action(&alice, &GovAction::Upgrade(new_wasm_hash.clone()));
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("UPGRADED"),).into_val(&env),
+                (Some(deployed_wasm_hash(&env)), new_wasm_hash.clone()).into_val(&env),
+            )
+        ]
+    );
+    let stored_hash = env.as_contract(&contract_id, || common::upgrade::get_wasm_hash(&env));
+    assert_eq!(stored_hash, Some(new_wasm_hash));
+}
+
+/// Test: Migrates storage once per version
+/// Verifies that migrate bumps an untracked deployment to the current version and then refuses to run again.
+/// Receives: Instance without a stored version. Returns: AlreadyMigrated error (#16) when up to date. Validates the MIGRATED event and stored version.
+#[test]
+fn it_migrates_once_per_version() {
+    let env = Env::default();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(ReputationContract, (admin.clone(), deployed_wasm_hash(&env)));
+    let client = ReputationContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert_eq!(client.try_migrate(), Err(Ok(ReputationError::AlreadyMigrated.into())));
+
+    // Deployed before versions were tracked
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&common::upgrade::VERSION_KEY);
+    });
+    assert_eq!(client.get_version(), 0);
+
+    assert_eq!(client.migrate(), CONTRACT_VERSION);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("MIGRATED"),).into_val(&env),
+                (0u32, CONTRACT_VERSION).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert_eq!(client.try_migrate(), Err(Ok(ReputationError::AlreadyMigrated.into())));
+}
@@ -1,4 +1,11 @@
-use soroban_sdk::{Address, Symbol};
+use soroban_sdk::{contracttype, Address, Symbol};
+
+// Storage keys for per-address entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Score(Address),
+}
 
 // Score change event data structure
 #[allow(dead_code)]
@@ -23,7 +30,52 @@ pub struct AdminChanged {
     pub new_admin: Address,
 }
 
+// Score rewards applied for repayment behavior
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardParams {
+    pub on_time: u32,    // Installment paid by its due date
+    pub early: u32,      // Installment paid well before its due date
+    pub completion: u32, // Loan fully repaid
+}
+
+// Score penalties applied for poor repayment behavior
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PenaltyParams {
+    pub late: u32,        // Installment past its grace period
+    pub missed: u32,      // Installment still unpaid a week after its due date
+    pub restructure: u32, // Loan rescheduled instead of defaulting
+}
+
+// Persistent entry TTL (in ledgers)
+pub const DAY_IN_LEDGERS: u32 = 17280;
+pub const SCORE_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const SCORE_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+
+// Pause flags, each pausing one group of score updates
+pub const PAUSE_REWARDS: u32 = 1 << 0; // increase_score, reward_on_time, reward_early, reward_completion
+pub const PAUSE_PENALTIES: u32 = 1 << 1; // decrease_score, slash, penalize_late, penalize_missed, penalize_restructure
+pub const PAUSE_SET_SCORE: u32 = 1 << 2; // set_score
+pub const PAUSE_ALL: u32 = PAUSE_REWARDS | PAUSE_PENALTIES | PAUSE_SET_SCORE;
+
+// Storage layout version of this code, see `migrate`
+pub const CONTRACT_VERSION: u32 = 1;
+
 // Constants for score bounds
 pub const MIN_SCORE: u32 = 0;
 pub const MAX_SCORE: u32 = 100;
 
+// Default score penalty applied when a loan defaults
+pub const DEFAULT_PENALTY: u32 = 25;
+
+// Default score rewards for repayment behavior
+pub const DEFAULT_ON_TIME_REWARD: u32 = 3;
+pub const DEFAULT_EARLY_REWARD: u32 = 7;
+pub const DEFAULT_COMPLETION_REWARD: u32 = 12;
+
+// Default score penalties for poor repayment behavior
+pub const DEFAULT_LATE_PENALTY: u32 = 3;
+pub const DEFAULT_MISSED_PENALTY: u32 = 7;
+pub const DEFAULT_RESTRUCTURE_PENALTY: u32 = 10;
+